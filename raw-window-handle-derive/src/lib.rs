@@ -0,0 +1,439 @@
+//! Derive macros for the [`raw-window-handle`](https://docs.rs/raw-window-handle) traits.
+//!
+//! The macros are also re-exported from `raw-window-handle` when its `derive` feature is enabled.
+//!
+//! The derives implement a trait for a wrapper struct by delegating to exactly one of its fields,
+//! which is marked with an attribute:
+//!
+//! ```
+//! use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle, HasWindowHandle};
+//! # use raw_window_handle::{ActiveHandle, HandleError, RawDisplayHandle, RawWindowHandle, WindowHandle};
+//! # use raw_window_handle::unix::{XlibDisplayHandle, XlibHandle};
+//! # struct Window;
+//! # unsafe impl HasRawWindowHandle for Window {
+//! #     fn raw_window_handle(&self) -> RawWindowHandle {
+//! #         RawWindowHandle::Xlib(XlibHandle::empty())
+//! #     }
+//! # }
+//! # unsafe impl HasRawDisplayHandle for Window {
+//! #     fn raw_display_handle(&self) -> RawDisplayHandle {
+//! #         RawDisplayHandle::Xlib(XlibDisplayHandle::empty())
+//! #     }
+//! # }
+//! # impl HasWindowHandle for Window {
+//! #     fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+//! #         Ok(unsafe { WindowHandle::borrow_raw(self.raw_window_handle(), ActiveHandle::new_unchecked()) })
+//! #     }
+//! # }
+//!
+//! #[derive(
+//!     raw_window_handle_derive::HasRawWindowHandle,
+//!     raw_window_handle_derive::HasRawDisplayHandle,
+//!     raw_window_handle_derive::HasWindowHandle,
+//! )]
+//! struct Viewport {
+//!     name: String,
+//!     #[raw_window_handle]
+//!     window: Window,
+//! }
+//!
+//! let viewport = Viewport { name: "main".into(), window: Window };
+//! assert_eq!(viewport.raw_window_handle(), viewport.window.raw_window_handle());
+//! assert_eq!(viewport.raw_display_handle(), viewport.window.raw_display_handle());
+//! assert!(viewport.window_handle().is_ok());
+//! ```
+//!
+//! `HasRawWindowHandle` and `HasWindowHandle` delegate to the field marked with
+//! `#[raw_window_handle]`. `HasRawDisplayHandle` delegates to the field marked with
+//! `#[raw_display_handle]`, or to the `#[raw_window_handle]` field if no field is marked with it.
+//!
+//! Exactly one field must be marked:
+//!
+//! ```compile_fail
+//! # use raw_window_handle::{HasRawWindowHandle, WindowHandle};
+//! #[derive(raw_window_handle_derive::HasRawWindowHandle)]
+//! struct Viewport<'a> {
+//!     window: WindowHandle<'a>,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use raw_window_handle::{HasRawWindowHandle, WindowHandle};
+//! #[derive(raw_window_handle_derive::HasRawWindowHandle)]
+//! struct Viewport<'a> {
+//!     #[raw_window_handle]
+//!     left: WindowHandle<'a>,
+//!     #[raw_window_handle]
+//!     right: WindowHandle<'a>,
+//! }
+//! ```
+//!
+//! Generic and tuple structs are supported, the marked field's type must implement the trait:
+//!
+//! ```
+//! # use raw_window_handle::HasRawWindowHandle;
+//! #[derive(raw_window_handle_derive::HasRawWindowHandle)]
+//! struct Wrapper<W: HasRawWindowHandle>(u32, #[raw_window_handle] W);
+//! ```
+//!
+//! Bounds may bind associated types, and parameters may have defaults:
+//!
+//! ```
+//! # use raw_window_handle::{ActiveHandle, HasRawWindowHandle, RawWindowHandle, WindowHandle};
+//! # use raw_window_handle::unix::XlibHandle;
+//! #[derive(raw_window_handle_derive::HasRawWindowHandle)]
+//! struct Window<W: HasRawWindowHandle, I: Iterator<Item = u8>, F: Fn() -> u8 = fn() -> u8> {
+//!     #[raw_window_handle]
+//!     window: W,
+//!     pixels: I,
+//!     callback: F,
+//! }
+//!
+//! let raw = RawWindowHandle::Xlib(XlibHandle::empty());
+//! let window: Window<_, _> = Window {
+//!     window: unsafe { WindowHandle::borrow_raw(raw, ActiveHandle::new_unchecked()) },
+//!     pixels: core::iter::empty(),
+//!     callback: || 0,
+//! };
+//! assert_eq!(window.raw_window_handle(), raw);
+//! ```
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// Derive `HasRawWindowHandle` by delegating to the field marked with `#[raw_window_handle]`.
+#[proc_macro_derive(HasRawWindowHandle, attributes(raw_window_handle))]
+pub fn derive_has_raw_window_handle(input: TokenStream) -> TokenStream {
+    expand(
+        input,
+        "HasRawWindowHandle",
+        &["raw_window_handle"],
+        |field| {
+            format!(
+                "fn raw_window_handle(&self) -> ::raw_window_handle::RawWindowHandle {{
+                ::raw_window_handle::HasRawWindowHandle::raw_window_handle(&self.{field})
+            }}
+            fn try_raw_window_handle(&self) -> ::core::result::Result<
+                ::raw_window_handle::RawWindowHandle,
+                ::raw_window_handle::HandleError,
+            > {{
+                ::raw_window_handle::HasRawWindowHandle::try_raw_window_handle(&self.{field})
+            }}",
+                field = field
+            )
+        },
+    )
+}
+
+/// Derive `HasRawDisplayHandle` by delegating to the field marked with `#[raw_display_handle]`,
+/// or with `#[raw_window_handle]` if no field is marked with `#[raw_display_handle]`.
+#[proc_macro_derive(HasRawDisplayHandle, attributes(raw_display_handle, raw_window_handle))]
+pub fn derive_has_raw_display_handle(input: TokenStream) -> TokenStream {
+    expand(
+        input,
+        "HasRawDisplayHandle",
+        &["raw_display_handle", "raw_window_handle"],
+        |field| {
+            format!(
+                "fn raw_display_handle(&self) -> ::raw_window_handle::RawDisplayHandle {{
+                    ::raw_window_handle::HasRawDisplayHandle::raw_display_handle(&self.{field})
+                }}",
+                field = field
+            )
+        },
+    )
+}
+
+/// Derive `HasWindowHandle` by delegating to the field marked with `#[raw_window_handle]`.
+#[proc_macro_derive(HasWindowHandle, attributes(raw_window_handle))]
+pub fn derive_has_window_handle(input: TokenStream) -> TokenStream {
+    expand(input, "HasWindowHandle", &["raw_window_handle"], |field| {
+        format!(
+            "fn window_handle(&self) -> ::core::result::Result<
+                ::raw_window_handle::WindowHandle<'_>,
+                ::raw_window_handle::HandleError,
+            > {{
+                ::raw_window_handle::HasWindowHandle::window_handle(&self.{field})
+            }}",
+            field = field
+        )
+    })
+}
+
+/// Generate an impl of `trait_name` whose body is produced by `body` from the delegated field.
+///
+/// `attrs` lists the field attributes in order of preference.
+fn expand(
+    input: TokenStream,
+    trait_name: &str,
+    attrs: &[&str],
+    body: impl FnOnce(&str) -> String,
+) -> TokenStream {
+    let item = match Struct::parse(input) {
+        Ok(item) => item,
+        Err(message) => return compile_error(&format!("{}: {}", trait_name, message)),
+    };
+
+    let mut field = None;
+    for attr in attrs {
+        let marked: Vec<&Field> = item
+            .fields
+            .iter()
+            .filter(|field| field.attrs.iter().any(|a| a == attr))
+            .collect();
+        match marked.len() {
+            0 => continue,
+            1 => {
+                field = Some(marked[0].name.clone());
+                break;
+            }
+            _ => {
+                return compile_error(&format!(
+                    "cannot derive `{}` for `{}`: only one field may be marked with `#[{}]`",
+                    trait_name, item.name, attr
+                ))
+            }
+        }
+    }
+    let field = match field {
+        Some(field) => field,
+        None => {
+            return compile_error(&format!(
+                "cannot derive `{}` for `{}`: mark the field to delegate to with `#[{}]`",
+                trait_name, item.name, attrs[0]
+            ))
+        }
+    };
+
+    let unsafety = if trait_name.starts_with("HasRaw") {
+        "unsafe "
+    } else {
+        ""
+    };
+    format!(
+        "{unsafety}impl<{impl_generics}> ::raw_window_handle::{trait_name} for {name}<{type_generics}> {where_clause} {{ {body} }}",
+        unsafety = unsafety,
+        impl_generics = item.impl_generics.join(", "),
+        trait_name = trait_name,
+        name = item.name,
+        type_generics = item.type_generics.join(", "),
+        where_clause = item.where_clause,
+        body = body(&field),
+    )
+    .parse()
+    .expect("generated impl is valid Rust")
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("::core::compile_error!({:?});", message)
+        .parse()
+        .expect("compile_error! invocation is valid Rust")
+}
+
+struct Field {
+    /// The field's name, or its index in a tuple struct.
+    name: String,
+    /// The names of the field's attributes that consist of a single identifier.
+    attrs: Vec<String>,
+}
+
+struct Struct {
+    name: String,
+    impl_generics: Vec<String>,
+    type_generics: Vec<String>,
+    where_clause: String,
+    fields: Vec<Field>,
+}
+
+impl Struct {
+    fn parse(input: TokenStream) -> Result<Struct, &'static str> {
+        let mut tokens = input.into_iter().peekable();
+
+        // Outer attributes and visibility.
+        loop {
+            match tokens.peek() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '#' => {
+                    tokens.next();
+                    tokens.next();
+                }
+                Some(TokenTree::Ident(i)) if i.to_string() == "pub" => {
+                    tokens.next();
+                    if let Some(TokenTree::Group(g)) = tokens.peek() {
+                        if g.delimiter() == Delimiter::Parenthesis {
+                            tokens.next();
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        match tokens.next() {
+            Some(TokenTree::Ident(i)) if i.to_string() == "struct" => {}
+            _ => return Err("can only be derived for structs"),
+        }
+        let name = match tokens.next() {
+            Some(TokenTree::Ident(i)) => i.to_string(),
+            _ => return Err("expected a struct name"),
+        };
+
+        let mut params = Vec::new();
+        if let Some(TokenTree::Punct(p)) = tokens.peek() {
+            if p.as_char() == '<' {
+                tokens.next();
+                let mut depth = 0;
+                let mut arrow = false;
+                let mut current = Vec::new();
+                for token in tokens.by_ref() {
+                    let after_minus = std::mem::replace(&mut arrow, is_arrow_start(&token));
+                    if let TokenTree::Punct(p) = &token {
+                        match p.as_char() {
+                            '<' => depth += 1,
+                            '>' if after_minus => {}
+                            '>' if depth == 0 => break,
+                            '>' => depth -= 1,
+                            ',' if depth == 0 => {
+                                params.push(std::mem::take(&mut current));
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    }
+                    current.push(token);
+                }
+                if !current.is_empty() {
+                    params.push(current);
+                }
+            }
+        }
+
+        let mut where_clause = TokenStream::new();
+        let mut fields = Vec::new();
+        for token in tokens {
+            match token {
+                TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+                    fields = parse_fields(g.stream(), true);
+                    break;
+                }
+                TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis => {
+                    fields = parse_fields(g.stream(), false);
+                }
+                TokenTree::Punct(p) if p.as_char() == ';' => break,
+                token => where_clause.extend(Some(token)),
+            }
+        }
+        if fields.is_empty() {
+            return Err("can only be derived for structs with fields");
+        }
+
+        let mut impl_generics = Vec::new();
+        let mut type_generics = Vec::new();
+        for param in params {
+            let (impl_param, type_param) = split_param(param);
+            impl_generics.push(impl_param);
+            type_generics.push(type_param);
+        }
+
+        Ok(Struct {
+            name,
+            impl_generics,
+            type_generics,
+            where_clause: where_clause.to_string(),
+            fields,
+        })
+    }
+}
+
+/// Split a generic parameter into its declaration without default, and its name.
+fn split_param(param: Vec<TokenTree>) -> (String, String) {
+    let mut declaration = TokenStream::new();
+    // The default starts at the first `=` outside of angle brackets, others bind associated types
+    // in the bounds, as in `I: Iterator<Item = u8>`.
+    let mut depth = 0;
+    let mut arrow = false;
+    for token in param.iter() {
+        let after_minus = std::mem::replace(&mut arrow, is_arrow_start(token));
+        if let TokenTree::Punct(p) = token {
+            match p.as_char() {
+                '<' => depth += 1,
+                '>' if !after_minus => depth -= 1,
+                '=' if depth == 0 => break,
+                _ => {}
+            }
+        }
+        declaration.extend(Some(token.clone()));
+    }
+
+    let name = match (&param[0], param.get(1)) {
+        (TokenTree::Punct(p), Some(lifetime)) if p.as_char() == '\'' => format!("'{}", lifetime),
+        (TokenTree::Ident(i), Some(name)) if i.to_string() == "const" => name.to_string(),
+        (first, _) => first.to_string(),
+    };
+    (declaration.to_string(), name)
+}
+
+fn is_arrow_start(token: &TokenTree) -> bool {
+    match token {
+        TokenTree::Punct(p) => p.as_char() == '-' && p.spacing() == Spacing::Joint,
+        _ => false,
+    }
+}
+
+fn parse_fields(stream: TokenStream, named: bool) -> Vec<Field> {
+    let mut fields = Vec::new();
+    let mut attrs = Vec::new();
+    let mut name = None;
+    // Whether the tokens seen so far in the current field are only attributes and visibility.
+    let mut in_prefix = true;
+    let mut depth = 0;
+
+    // Whether the previous token starts a `->`, whose `>` is not a closing angle bracket.
+    let mut arrow = false;
+
+    let mut tokens = stream.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let after_minus = std::mem::replace(&mut arrow, is_arrow_start(&token));
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == '#' && in_prefix => {
+                if let Some(TokenTree::Group(g)) = tokens.next() {
+                    let inner: Vec<TokenTree> = g.stream().into_iter().collect();
+                    if let [TokenTree::Ident(i)] = inner.as_slice() {
+                        attrs.push(i.to_string());
+                    }
+                }
+                continue;
+            }
+            TokenTree::Ident(i) if i.to_string() == "pub" && in_prefix => {
+                if let Some(TokenTree::Group(g)) = tokens.peek() {
+                    if g.delimiter() == Delimiter::Parenthesis {
+                        tokens.next();
+                    }
+                }
+                continue;
+            }
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' && !after_minus && depth > 0 => depth -= 1,
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => {
+                let index = fields.len().to_string();
+                fields.push(Field {
+                    name: name.take().unwrap_or(index),
+                    attrs: std::mem::take(&mut attrs),
+                });
+                in_prefix = true;
+                continue;
+            }
+            _ => {}
+        }
+        if in_prefix && named {
+            name = Some(token.to_string());
+        }
+        in_prefix = false;
+    }
+    if !in_prefix {
+        let index = fields.len().to_string();
+        fields.push(Field {
+            name: name.unwrap_or(index),
+            attrs,
+        });
+    }
+    fields
+}
@@ -13,6 +13,11 @@ use core::ptr::NonNull;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct AndroidHandle {
     /// A pointer to an ANativeWindow.
+    ///
+    /// The native window is destroyed whenever the application is suspended. Providers should
+    /// report [`HandleError::Unavailable`](crate::HandleError::Unavailable) from
+    /// `try_raw_window_handle` while no native window exists, rather than handing out an empty
+    /// handle. [`Active`](crate::Active) can be used to keep track of this state.
     pub a_native_window: Option<NonNull<c_void>>,
 }
 
@@ -23,3 +28,20 @@ impl AndroidHandle {
         }
     }
 }
+
+/// Raw display handle for Android.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::android::AndroidDisplayHandle;
+/// let handle = AndroidDisplayHandle::empty();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AndroidDisplayHandle {}
+
+impl AndroidDisplayHandle {
+    pub fn empty() -> AndroidDisplayHandle {
+        AndroidDisplayHandle {}
+    }
+}
@@ -0,0 +1,285 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{HandleError, HasRawWindowHandle, RawWindowHandle};
+
+/// Keeps track of whether the handles of a window are currently usable.
+///
+/// Some platforms destroy the native surface behind a window while the window itself stays alive,
+/// e.g. Android destroys the `ANativeWindow` whenever the application is suspended, and Linux
+/// providers may want to treat unmapped windows the same way. Providers keep an `Active` next to
+/// their window and only hand out [`WindowHandle`]s while it is active.
+///
+/// A new `Active` starts out inactive.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{Active, HandleError, HasWindowHandle, RawWindowHandle, WindowHandle};
+/// # use raw_window_handle::android::AndroidHandle;
+/// struct Window {
+///     active: Active,
+///     raw: RawWindowHandle,
+/// }
+///
+/// impl HasWindowHandle for Window {
+///     fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+///         let active = self.active.handle().ok_or(HandleError::Unavailable)?;
+///         // Safety: `raw` stays valid for as long as `self` is alive and active.
+///         Ok(unsafe { WindowHandle::borrow_raw(self.raw, active) })
+///     }
+/// }
+///
+/// let mut window = Window {
+///     active: Active::new(),
+///     raw: RawWindowHandle::Android(AndroidHandle::empty()),
+/// };
+/// assert_eq!(window.window_handle().err(), Some(HandleError::Unavailable));
+///
+/// // Resumed: the native window has been created.
+/// unsafe { window.active.set_active() };
+/// assert!(window.active.is_active());
+/// assert!(window.window_handle().is_ok());
+/// assert_eq!(window.active.with_active_handle(|_| 1), Ok(1));
+///
+/// // Suspended: the native window is about to be destroyed.
+/// window.active.set_inactive();
+/// assert_eq!(window.window_handle().err(), Some(HandleError::Unavailable));
+/// assert_eq!(window.active.with_active_handle(|_| 1), Err(HandleError::Unavailable));
+/// ```
+pub struct Active {
+    active: AtomicBool,
+}
+
+impl Active {
+    /// Create a new, inactive `Active`.
+    pub const fn new() -> Self {
+        Self {
+            active: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the handles guarded by this `Active` are currently usable.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Get an [`ActiveHandle`] if the handles guarded by this `Active` are currently usable.
+    pub fn handle(&self) -> Option<ActiveHandle<'_>> {
+        if self.is_active() {
+            Some(ActiveHandle {
+                _marker: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Run `f` with an [`ActiveHandle`], or fail with [`HandleError::Unavailable`] if the handles
+    /// guarded by this `Active` aren't usable right now.
+    pub fn with_active_handle<R>(
+        &self,
+        f: impl FnOnce(ActiveHandle<'_>) -> R,
+    ) -> Result<R, HandleError> {
+        self.handle().map(f).ok_or(HandleError::Unavailable)
+    }
+
+    /// Mark the guarded handles as usable.
+    ///
+    /// ## Safety
+    /// The native surfaces behind the guarded handles must exist, and must keep existing until
+    /// this `Active` is marked inactive again.
+    pub unsafe fn set_active(&self) {
+        self.active.store(true, Ordering::Release);
+    }
+
+    /// Mark the guarded handles as unusable.
+    ///
+    /// Taking `&mut self` guarantees that no [`ActiveHandle`] borrowed from this `Active`, and
+    /// thus no [`WindowHandle`] created with one, is still alive.
+    pub fn set_inactive(&mut self) {
+        *self.active.get_mut() = false;
+    }
+
+    /// Mark the guarded handles as unusable through a shared reference.
+    ///
+    /// ## Safety
+    /// No [`ActiveHandle`] borrowed from this `Active`, and no [`WindowHandle`] created with one,
+    /// may be used after this call.
+    pub unsafe fn set_inactive_unchecked(&self) {
+        self.active.store(false, Ordering::Release);
+    }
+}
+
+impl Default for Active {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Active {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Active")
+            .field("active", &self.is_active())
+            .finish()
+    }
+}
+
+/// Proof that the handles guarded by an [`Active`] are usable for the lifetime `'a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActiveHandle<'a> {
+    _marker: PhantomData<&'a Active>,
+}
+
+impl ActiveHandle<'_> {
+    /// Create an `ActiveHandle` that isn't tied to any [`Active`].
+    ///
+    /// This is meant for providers on platforms where handles can't become unusable while the
+    /// window is alive.
+    ///
+    /// ## Safety
+    /// The handles that this `ActiveHandle` is used with must be usable for its whole lifetime.
+    pub const unsafe fn new_unchecked() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A window handle that borrows from the window that provided it.
+///
+/// Unlike [`RawWindowHandle`], this type can't outlive the window it was obtained from, so the
+/// borrow checker enforces the lifetime requirement described in [`HasRawWindowHandle`].
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::{ActiveHandle, HandleError, HasWindowHandle, RawWindowHandle, WindowHandle};
+/// struct Window {
+///     raw: Option<RawWindowHandle>,
+/// }
+///
+/// impl HasWindowHandle for Window {
+///     fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+///         let raw = self.raw.ok_or(HandleError::Unavailable)?;
+///         // Safety: `raw` stays valid for as long as `self` is alive, and this platform never
+///         // invalidates it while the window is alive.
+///         Ok(unsafe { WindowHandle::borrow_raw(raw, ActiveHandle::new_unchecked()) })
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowHandle<'a> {
+    raw: RawWindowHandle,
+    _active: ActiveHandle<'a>,
+    _marker: PhantomData<&'a *const ()>,
+}
+
+impl<'a> WindowHandle<'a> {
+    /// Assert that the `RawWindowHandle` value can be trusted for the lifetime `'a`.
+    ///
+    /// The [`ActiveHandle`] ties the returned handle to the period in which the window's handles
+    /// are usable.
+    ///
+    /// ## Safety
+    /// The handle must uphold the guarantees given in the [`HasRawWindowHandle`] trait, and must
+    /// remain valid for the lifetime `'a`.
+    pub const unsafe fn borrow_raw(raw: RawWindowHandle, active: ActiveHandle<'a>) -> Self {
+        Self {
+            raw,
+            _active: active,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the underlying raw window handle.
+    pub const fn raw(&self) -> RawWindowHandle {
+        self.raw
+    }
+
+    /// Assert that the `RawWindowHandle` value can be trusted for the lifetime `'a`.
+    ///
+    /// ## Safety
+    /// The handle must uphold the guarantees given in the [`HasRawWindowHandle`] trait, and must
+    /// remain valid and usable for the lifetime `'a`.
+    #[deprecated(note = "use `WindowHandle::borrow_raw` instead")]
+    pub const unsafe fn new(raw: RawWindowHandle) -> Self {
+        Self::borrow_raw(raw, ActiveHandle::new_unchecked())
+    }
+}
+
+/// The previous name of [`WindowHandle`].
+///
+/// ## Example
+/// ```
+/// # #![allow(deprecated)]
+/// # use raw_window_handle::{HasRawWindowHandle, RawWindowHandle, TrustedWindowHandle};
+/// # use raw_window_handle::unix::XlibHandle;
+/// let raw = RawWindowHandle::Xlib(XlibHandle::empty());
+/// let handle: TrustedWindowHandle<'static> = unsafe { TrustedWindowHandle::new(raw) };
+/// assert_eq!(handle.raw_window_handle(), raw);
+/// ```
+#[deprecated(note = "use `WindowHandle` and `WindowHandle::borrow_raw` instead")]
+pub type TrustedWindowHandle<'a> = WindowHandle<'a>;
+
+impl fmt::Debug for WindowHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WindowHandle").field(&self.raw).finish()
+    }
+}
+
+unsafe impl HasRawWindowHandle for WindowHandle<'_> {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.raw
+    }
+}
+
+/// A window that can hand out a [`WindowHandle`] borrowing from itself.
+///
+/// This trait is safe to implement, since creating a [`WindowHandle`] requires an `unsafe` call to
+/// [`WindowHandle::borrow_raw`]. If no valid handle is available, implementers return a
+/// [`HandleError`] describing why.
+pub trait HasWindowHandle {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError>;
+}
+
+impl HasWindowHandle for WindowHandle<'_> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        Ok(*self)
+    }
+}
+
+impl<T: HasWindowHandle + ?Sized> HasWindowHandle for &T {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        (**self).window_handle()
+    }
+}
+
+impl<T: HasWindowHandle + ?Sized> HasWindowHandle for &mut T {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        (**self).window_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+impl<T: HasWindowHandle + ?Sized> HasWindowHandle for alloc::boxed::Box<T> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        (**self).window_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+impl<T: HasWindowHandle + ?Sized> HasWindowHandle for alloc::rc::Rc<T> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        (**self).window_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+impl<T: HasWindowHandle + ?Sized> HasWindowHandle for alloc::sync::Arc<T> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        (**self).window_handle()
+    }
+}
@@ -0,0 +1,277 @@
+use core::convert::TryFrom;
+use core::ffi::c_void;
+use core::fmt;
+use core::num::{NonZeroU32, NonZeroU64};
+use core::ptr::NonNull;
+
+use crate::portal::parse_hex_id;
+use crate::unix::{XcbHandle, XlibHandle};
+use crate::windows::WindowsHandle;
+use crate::{HandleError, RawDisplayHandle, RawWindowHandle};
+
+/// A window handle that can be passed to another process, and imported there.
+///
+/// The [`Display`](fmt::Display) impl formats the handle as a token that
+/// [`parse`](Self::parse) turns back into an equal value:
+///
+/// - `x11:0x3a00007::0`: the X11 window ID, followed by the name of the X server.
+/// - `wayland:<handle>`: a handle from the `zxdg_exporter_v2` protocol.
+/// - `win32:0x1d0a2c`: an `HWND`.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{ExportedWindowHandle, RawWindowHandle};
+/// # use raw_window_handle::unix::XlibHandle;
+/// let raw = RawWindowHandle::Xlib(XlibHandle::new(0x3a00007, 0x1000 as *mut _).unwrap());
+/// let exported = ExportedWindowHandle::export(raw, ":0").unwrap();
+/// let token = exported.to_string();
+/// assert_eq!(token, "x11:0x3a00007::0");
+///
+/// // In the receiving process:
+/// let imported = ExportedWindowHandle::parse(&token).unwrap();
+/// assert_eq!(imported, exported);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportedWindowHandle<'a> {
+    /// An X11 window, which any connection to the same X server can use.
+    X11 {
+        /// The X11 window ID.
+        window: NonZeroU32,
+        /// The name of the X server, as in `$DISPLAY`.
+        display_name: &'a str,
+    },
+    /// A Wayland toplevel exported with `zxdg_exporter_v2`, to be imported with
+    /// `zxdg_importer_v2`.
+    Wayland {
+        /// The handle from the `zxdg_exported_v2.handle` event.
+        handle: &'a str,
+    },
+    /// A Win32 window, which is valid in every process of the same desktop session.
+    Win32 {
+        /// The `HWND` value.
+        hwnd: NonZeroU64,
+    },
+}
+
+impl<'a> ExportedWindowHandle<'a> {
+    /// Export a window handle for use in another process.
+    ///
+    /// `display_name` is the name the window's X server connection was opened with, and is
+    /// ignored for other handles. Wayland surfaces have to be exported with `zxdg_exporter_v2`,
+    /// whose handle can then be passed as [`ExportedWindowHandle::Wayland`]; for them this fails
+    /// with [`HandleError::NotSupported`], as it does for handles that are only valid in the
+    /// process that created them.
+    pub fn export(raw: RawWindowHandle, display_name: &'a str) -> Result<Self, HandleError> {
+        let window = match raw {
+            RawWindowHandle::Xlib(handle) => match handle.window {
+                Some(window) => u32::try_from(window.get())
+                    .ok()
+                    .and_then(NonZeroU32::new)
+                    .ok_or(HandleError::NotSupported)?,
+                None => return Err(HandleError::Unavailable),
+            },
+            RawWindowHandle::Xcb(handle) => handle.window.ok_or(HandleError::Unavailable)?,
+            RawWindowHandle::Windows(handle) => {
+                let hwnd = handle.hwnd.ok_or(HandleError::Unavailable)?;
+                let hwnd = NonZeroU64::new(hwnd.as_ptr() as usize as u64)
+                    .expect("non-null pointers are nonzero");
+                return Ok(ExportedWindowHandle::Win32 { hwnd });
+            }
+            _ => return Err(HandleError::NotSupported),
+        };
+        Ok(ExportedWindowHandle::X11 {
+            window,
+            display_name,
+        })
+    }
+
+    /// Parse a token produced by the [`Display`](fmt::Display) impl.
+    ///
+    /// ## Example
+    /// ```
+    /// # use raw_window_handle::{ExportedWindowHandle, ParseExportedWindowHandleError};
+    /// for token in ["x11:0x3a00007::0", "x11:0x1:localhost:10.0", "wayland:abc", "win32:0x1d0a2c"] {
+    ///     let exported = ExportedWindowHandle::parse(token).unwrap();
+    ///     assert_eq!(exported.to_string(), token);
+    /// }
+    ///
+    /// use ParseExportedWindowHandleError::*;
+    /// for (token, err) in [
+    ///     ("", UnknownKind),
+    ///     ("gbm:0x1", UnknownKind),
+    ///     ("x11:0x1", EmptyToken),
+    ///     ("x11:0x1:", EmptyToken),
+    ///     ("wayland:", EmptyToken),
+    ///     ("win32:", EmptyToken),
+    ///     ("x11:0x100000000::0", InvalidId),
+    ///     ("x11:0::0", InvalidId),
+    ///     ("win32:hwnd", InvalidId),
+    /// ] {
+    ///     assert_eq!(ExportedWindowHandle::parse(token), Err(err), "{:?}", token);
+    /// }
+    /// ```
+    pub fn parse(token: &'a str) -> Result<Self, ParseExportedWindowHandleError> {
+        let (kind, rest) = match token.find(':') {
+            Some(colon) => (&token[..colon], &token[colon + 1..]),
+            None => return Err(ParseExportedWindowHandleError::UnknownKind),
+        };
+        if rest.is_empty() {
+            return match kind {
+                "x11" | "wayland" | "win32" => Err(ParseExportedWindowHandleError::EmptyToken),
+                _ => Err(ParseExportedWindowHandleError::UnknownKind),
+            };
+        }
+
+        match kind {
+            "x11" => {
+                let (window, display_name) = match rest.find(':') {
+                    Some(colon) => (&rest[..colon], &rest[colon + 1..]),
+                    None => return Err(ParseExportedWindowHandleError::EmptyToken),
+                };
+                if display_name.is_empty() {
+                    return Err(ParseExportedWindowHandleError::EmptyToken);
+                }
+                let window = parse_hex_id(window)
+                    .and_then(|window| u32::try_from(window.get()).ok())
+                    .and_then(NonZeroU32::new)
+                    .ok_or(ParseExportedWindowHandleError::InvalidId)?;
+                Ok(ExportedWindowHandle::X11 {
+                    window,
+                    display_name,
+                })
+            }
+            "wayland" => Ok(ExportedWindowHandle::Wayland { handle: rest }),
+            "win32" => parse_hex_id(rest)
+                .map(|hwnd| ExportedWindowHandle::Win32 { hwnd })
+                .ok_or(ParseExportedWindowHandleError::InvalidId),
+            _ => Err(ParseExportedWindowHandleError::UnknownKind),
+        }
+    }
+
+    /// Import the handle into this process, using a connection to the display that the caller
+    /// already has.
+    ///
+    /// No connection is opened: `display` is used as is, and `display_name` must be the name it was
+    /// opened with. Only `display_name` is compared with the X server the handle was exported
+    /// from, `display` itself is never checked against it. With the `x11-reconnect` feature,
+    /// `ExportedWindowHandle::reconnect` opens a connection to the right X server instead.
+    ///
+    /// Fails with [`HandleError::NotSupported`] if `display_name` names a different X server than
+    /// the one the handle was exported from, if `display` is for another windowing system, for
+    /// Wayland handles, which have to be imported with `zxdg_importer_v2`, and for `HWND`s that
+    /// don't fit in a pointer. Names that only differ in the screen number, like `:0` and `:0.1`,
+    /// refer to the same X server. `display_name` is ignored for non-X11 handles. The `screen` of
+    /// X11 handles is the default screen of `display`.
+    ///
+    /// The X server is not contacted, so the window may have been destroyed since it was exported.
+    ///
+    /// ## Example
+    /// ```
+    /// # use core::convert::TryFrom;
+    /// # use raw_window_handle::{ExportedWindowHandle, HandleError, RawDisplayHandle};
+    /// # use raw_window_handle::unix::{XlibDisplayHandle, XlibHandle};
+    /// let exported = ExportedWindowHandle::parse("x11:0x3a00007::0").unwrap();
+    /// let mut display = XlibDisplayHandle::empty();
+    /// display.display = core::ptr::NonNull::new(0x1000 as *mut _);
+    /// display.screen = 1;
+    /// let display = RawDisplayHandle::Xlib(display);
+    ///
+    /// let handle = XlibHandle::try_from(exported.import(display, ":0.1").unwrap()).unwrap();
+    /// assert_eq!(handle.window.map(|window| window.get()), Some(0x3a00007));
+    /// assert_eq!(handle.screen, Some(1));
+    ///
+    /// assert!(exported.import(display, ":0").is_ok());
+    /// assert_eq!(exported.import(display, ":1"), Err(HandleError::NotSupported));
+    /// assert_eq!(exported.import(display, "remote:0"), Err(HandleError::NotSupported));
+    /// ```
+    pub fn import(
+        &self,
+        display: RawDisplayHandle,
+        display_name: &str,
+    ) -> Result<RawWindowHandle, HandleError> {
+        if let ExportedWindowHandle::X11 {
+            display_name: exported_name,
+            ..
+        } = *self
+        {
+            if x11_server(exported_name) != x11_server(display_name) {
+                return Err(HandleError::NotSupported);
+            }
+        }
+
+        match (*self, display) {
+            (ExportedWindowHandle::X11 { window, .. }, RawDisplayHandle::Xlib(display)) => {
+                let mut handle = XlibHandle::empty();
+                handle.window = Some(window.into());
+                handle.display = Some(display.display.ok_or(HandleError::Unavailable)?);
+                handle.screen = Some(display.screen);
+                Ok(RawWindowHandle::Xlib(handle))
+            }
+            (ExportedWindowHandle::X11 { window, .. }, RawDisplayHandle::Xcb(display)) => {
+                let mut handle = XcbHandle::empty();
+                handle.window = Some(window);
+                handle.connection = Some(display.connection.ok_or(HandleError::Unavailable)?);
+                handle.screen = Some(display.screen);
+                Ok(RawWindowHandle::Xcb(handle))
+            }
+            (ExportedWindowHandle::Win32 { hwnd }, RawDisplayHandle::Windows(_)) => {
+                let mut handle = WindowsHandle::empty();
+                let hwnd = usize::try_from(hwnd.get()).map_err(|_| HandleError::NotSupported)?;
+                handle.hwnd = NonNull::new(hwnd as *mut c_void);
+                Ok(RawWindowHandle::Windows(handle))
+            }
+            _ => Err(HandleError::NotSupported),
+        }
+    }
+}
+
+/// Strip the screen number from an X11 display name, leaving the part that names the X server.
+fn x11_server(display_name: &str) -> &str {
+    let colon = display_name.rfind(':').map_or(0, |colon| colon + 1);
+    match display_name[colon..].find('.') {
+        Some(dot) => &display_name[..colon + dot],
+        None => display_name,
+    }
+}
+
+impl fmt::Display for ExportedWindowHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportedWindowHandle::X11 {
+                window,
+                display_name,
+            } => write!(f, "x11:{:#x}:{}", window, display_name),
+            ExportedWindowHandle::Wayland { handle } => write!(f, "wayland:{}", handle),
+            ExportedWindowHandle::Win32 { hwnd } => write!(f, "win32:{:#x}", hwnd),
+        }
+    }
+}
+
+/// The error returned by [`ExportedWindowHandle::parse`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseExportedWindowHandleError {
+    /// The token doesn't start with `x11:`, `wayland:` or `win32:`.
+    UnknownKind,
+    /// A part of the token is missing.
+    EmptyToken,
+    /// The window ID or `HWND` isn't a nonzero hexadecimal number of the right size.
+    InvalidId,
+}
+
+impl fmt::Display for ParseExportedWindowHandleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseExportedWindowHandleError::UnknownKind => {
+                f.write_str("the token is neither `x11:`, `wayland:` nor `win32:`")
+            }
+            ParseExportedWindowHandleError::EmptyToken => f.write_str("the token is incomplete"),
+            ParseExportedWindowHandleError::InvalidId => {
+                f.write_str("the window ID is not a nonzero hexadecimal number of the right size")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseExportedWindowHandleError {}
@@ -0,0 +1,178 @@
+use core::num::NonZeroU32;
+
+/// The scale factor between a surface's logical and physical size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleFactor {
+    /// An integer scale factor, e.g. from `wl_output.scale` or Win32 at 200% scaling.
+    Integer(NonZeroU32),
+    /// A fractional scale factor, e.g. from `wp_fractional_scale_v1` or `Xft.dpi`.
+    Fractional(f64),
+}
+
+impl ScaleFactor {
+    /// Create a scale factor from a `wp_fractional_scale_v1.preferred_scale` numerator, which
+    /// is in units of 1/120. Returns `None` for a zero numerator.
+    ///
+    /// ```
+    /// # use core::num::NonZeroU32;
+    /// # use raw_window_handle::ScaleFactor;
+    /// assert_eq!(ScaleFactor::from_wayland_fractional(180), Some(ScaleFactor::Fractional(1.5)));
+    /// assert_eq!(
+    ///     ScaleFactor::from_wayland_fractional(240),
+    ///     Some(ScaleFactor::Integer(NonZeroU32::new(2).unwrap()))
+    /// );
+    /// assert_eq!(ScaleFactor::from_wayland_fractional(0), None);
+    /// ```
+    pub fn from_wayland_fractional(numerator: u32) -> Option<ScaleFactor> {
+        if numerator == 0 {
+            None
+        } else if numerator % 120 == 0 {
+            NonZeroU32::new(numerator / 120).map(ScaleFactor::Integer)
+        } else {
+            Some(ScaleFactor::Fractional(f64::from(numerator) / 120.0))
+        }
+    }
+
+    /// Create a fractional scale factor. Returns `None` unless `scale` is finite and positive.
+    ///
+    /// ```
+    /// # use raw_window_handle::ScaleFactor;
+    /// assert_eq!(ScaleFactor::fractional(1.25), Some(ScaleFactor::Fractional(1.25)));
+    /// assert_eq!(ScaleFactor::fractional(0.0), None);
+    /// assert_eq!(ScaleFactor::fractional(f64::NAN), None);
+    /// assert_eq!(ScaleFactor::fractional(f64::INFINITY), None);
+    /// ```
+    pub fn fractional(scale: f64) -> Option<ScaleFactor> {
+        if scale.is_finite() && scale > 0.0 {
+            Some(ScaleFactor::Fractional(scale))
+        } else {
+            None
+        }
+    }
+
+    /// Create a scale factor from a DPI value such as `Xft.dpi`, relative to 96 DPI. Returns
+    /// `None` unless `dpi` is finite and positive.
+    ///
+    /// ```
+    /// # use core::num::NonZeroU32;
+    /// # use raw_window_handle::ScaleFactor;
+    /// assert_eq!(ScaleFactor::from_dpi(96.0), Some(ScaleFactor::Integer(NonZeroU32::new(1).unwrap())));
+    /// assert_eq!(ScaleFactor::from_dpi(120.0), Some(ScaleFactor::Fractional(1.25)));
+    /// assert_eq!(ScaleFactor::from_dpi(0.0), None);
+    /// ```
+    pub fn from_dpi(dpi: f64) -> Option<ScaleFactor> {
+        let scale = dpi / 96.0;
+        if scale >= 1.0 && scale == f64::from(scale as u32) {
+            NonZeroU32::new(scale as u32).map(ScaleFactor::Integer)
+        } else {
+            ScaleFactor::fractional(scale)
+        }
+    }
+
+    /// Get the scale factor as a floating point value.
+    pub fn as_f64(self) -> f64 {
+        match self {
+            ScaleFactor::Integer(scale) => f64::from(scale.get()),
+            ScaleFactor::Fractional(scale) => scale,
+        }
+    }
+
+    /// The scale factor as a floating point value, if it is finite and positive.
+    fn checked_f64(self) -> Option<f64> {
+        ScaleFactor::fractional(self.as_f64()).map(ScaleFactor::as_f64)
+    }
+}
+
+/// The size and scale factor of a window's surface.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::{ScaleFactor, SurfaceGeometry};
+/// let geometry =
+///     SurfaceGeometry::from_logical_size(800.0, 600.0, ScaleFactor::Fractional(1.5)).unwrap();
+/// assert_eq!((geometry.physical_width, geometry.physical_height), (1200, 900));
+///
+/// let geometry =
+///     SurfaceGeometry::from_physical_size(1200, 900, ScaleFactor::Fractional(1.5)).unwrap();
+/// assert_eq!((geometry.logical_width, geometry.logical_height), (800.0, 600.0));
+///
+/// assert_eq!(SurfaceGeometry::from_physical_size(100, 100, ScaleFactor::Fractional(0.0)), None);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceGeometry {
+    /// The width of the surface in physical pixels, i.e. the size a swapchain should have.
+    pub physical_width: u32,
+    /// The height of the surface in physical pixels.
+    pub physical_height: u32,
+    /// The width of the surface in logical units.
+    pub logical_width: f64,
+    /// The height of the surface in logical units.
+    pub logical_height: f64,
+    /// The scale factor between the logical and the physical size.
+    pub scale_factor: ScaleFactor,
+}
+
+impl SurfaceGeometry {
+    /// Create the geometry of a surface from its physical size.
+    ///
+    /// Returns `None` unless the scale factor is finite and positive.
+    pub fn from_physical_size(
+        width: u32,
+        height: u32,
+        scale_factor: ScaleFactor,
+    ) -> Option<SurfaceGeometry> {
+        let scale = scale_factor.checked_f64()?;
+        Some(SurfaceGeometry {
+            physical_width: width,
+            physical_height: height,
+            logical_width: f64::from(width) / scale,
+            logical_height: f64::from(height) / scale,
+            scale_factor,
+        })
+    }
+
+    /// Create the geometry of a surface from its logical size.
+    ///
+    /// The physical size is rounded half away from zero, as required by `wp_fractional_scale_v1`.
+    /// Returns `None` unless the scale factor is finite and positive.
+    pub fn from_logical_size(
+        width: f64,
+        height: f64,
+        scale_factor: ScaleFactor,
+    ) -> Option<SurfaceGeometry> {
+        let scale = scale_factor.checked_f64()?;
+        Some(SurfaceGeometry {
+            physical_width: (width * scale + 0.5) as u32,
+            physical_height: (height * scale + 0.5) as u32,
+            logical_width: width,
+            logical_height: height,
+            scale_factor,
+        })
+    }
+}
+
+/// Window that knows the size and scale factor of its surface.
+///
+/// Implementing this next to [`HasRawWindowHandle`](crate::HasRawWindowHandle) lets consumers
+/// configure a swapchain from the same source that provided the handle.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{HasSurfaceGeometry, ScaleFactor, SurfaceGeometry};
+/// struct Window;
+///
+/// impl HasSurfaceGeometry for Window {
+///     fn surface_geometry(&self) -> SurfaceGeometry {
+///         let scale_factor = ScaleFactor::from_dpi(192.0).unwrap();
+///         SurfaceGeometry::from_physical_size(1920, 1080, scale_factor).unwrap()
+///     }
+/// }
+///
+/// let geometry = Window.surface_geometry();
+/// assert_eq!(geometry.scale_factor.as_f64(), 2.0);
+/// assert_eq!(geometry.logical_width, 960.0);
+/// ```
+pub trait HasSurfaceGeometry {
+    fn surface_geometry(&self) -> SurfaceGeometry;
+}
@@ -3,6 +3,9 @@ use core::ptr::NonNull;
 
 /// Raw window handle for iOS.
 ///
+/// The objects referred to by this handle may only be used on the main thread, see
+/// [`MainThreadMarker`](crate::MainThreadMarker).
+///
 /// ## Construction
 /// ```
 /// # use raw_window_handle::ios::IOSHandle;
@@ -26,3 +29,20 @@ impl IOSHandle {
         }
     }
 }
+
+/// Raw display handle for UIKit.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::ios::UiKitDisplayHandle;
+/// let handle = UiKitDisplayHandle::empty();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UiKitDisplayHandle {}
+
+impl UiKitDisplayHandle {
+    pub fn empty() -> UiKitDisplayHandle {
+        UiKitDisplayHandle {}
+    }
+}
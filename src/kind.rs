@@ -0,0 +1,183 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::{android, ios, linux, macos, redox, unix, web, windows, RawWindowHandle};
+
+macro_rules! handle_kinds {
+    ($($variant:ident($handle:ty),)*) => {
+        /// The variant of a [`RawWindowHandle`], without its data.
+        ///
+        /// ## Example
+        /// ```
+        /// # use core::convert::TryFrom;
+        /// # use raw_window_handle::{RawWindowHandle, RawWindowHandleKind};
+        /// # use raw_window_handle::unix::{WaylandHandle, XlibHandle};
+        /// let xlib = XlibHandle::new(42, 0x1000 as *mut _).unwrap();
+        /// let raw = RawWindowHandle::from(xlib);
+        /// assert_eq!(raw.kind(), RawWindowHandleKind::Xlib);
+        /// assert_eq!(raw.kind().to_string(), "Xlib");
+        ///
+        /// assert_eq!(XlibHandle::try_from(raw), Ok(xlib));
+        ///
+        /// let err = WaylandHandle::try_from(raw).unwrap_err();
+        /// assert_eq!(err.expected, RawWindowHandleKind::Wayland);
+        /// assert_eq!(err.found, RawWindowHandleKind::Xlib);
+        ///
+        /// #[cfg(target_os = "linux")]
+        /// assert!(RawWindowHandle::supported_kinds().contains(&RawWindowHandleKind::Xlib));
+        /// #[cfg(target_os = "windows")]
+        /// assert!(!RawWindowHandle::supported_kinds().contains(&RawWindowHandleKind::Xlib));
+        /// ```
+        #[non_exhaustive]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum RawWindowHandleKind {
+            $($variant,)*
+        }
+
+        impl RawWindowHandle {
+            /// The variant of this handle.
+            pub fn kind(&self) -> RawWindowHandleKind {
+                match self {
+                    $(RawWindowHandle::$variant(_) => RawWindowHandleKind::$variant,)*
+                }
+            }
+        }
+
+        $(
+            impl From<$handle> for RawWindowHandle {
+                fn from(handle: $handle) -> Self {
+                    RawWindowHandle::$variant(handle)
+                }
+            }
+
+            impl TryFrom<RawWindowHandle> for $handle {
+                type Error = WrongHandleKind;
+
+                fn try_from(raw: RawWindowHandle) -> Result<Self, Self::Error> {
+                    match raw {
+                        RawWindowHandle::$variant(handle) => Ok(handle),
+                        raw => Err(WrongHandleKind {
+                            expected: RawWindowHandleKind::$variant,
+                            found: raw.kind(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+handle_kinds! {
+    IOS(ios::IOSHandle),
+    MacOS(macos::MacOSHandle),
+    Orbital(redox::OrbitalHandle),
+    Xlib(unix::XlibHandle),
+    Xcb(unix::XcbHandle),
+    Wayland(unix::WaylandHandle),
+    Windows(windows::WindowsHandle),
+    WinRT(windows::WinRTHandle),
+    Web(web::WebHandle),
+    WebCanvas(web::WebCanvasHandle),
+    WebOffscreenCanvas(web::WebOffscreenCanvasHandle),
+    Android(android::AndroidHandle),
+    Drm(linux::DrmHandle),
+    Gbm(linux::GbmHandle),
+}
+
+impl fmt::Display for RawWindowHandleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl RawWindowHandle {
+    /// The kinds of handle that windowing systems on the current target can produce.
+    ///
+    /// Every variant is available on every target, this lists the ones that are meaningful here.
+    pub fn supported_kinds() -> &'static [RawWindowHandleKind] {
+        SUPPORTED_KINDS
+    }
+}
+
+#[cfg(target_os = "ios")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[RawWindowHandleKind::IOS];
+
+#[cfg(target_os = "macos")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[RawWindowHandleKind::MacOS];
+
+#[cfg(target_os = "redox")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[RawWindowHandleKind::Orbital];
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[
+    RawWindowHandleKind::Xlib,
+    RawWindowHandleKind::Xcb,
+    RawWindowHandleKind::Wayland,
+    RawWindowHandleKind::Drm,
+    RawWindowHandleKind::Gbm,
+];
+
+#[cfg(target_os = "solaris")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[
+    RawWindowHandleKind::Xlib,
+    RawWindowHandleKind::Xcb,
+    RawWindowHandleKind::Wayland,
+];
+
+#[cfg(target_os = "windows")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] =
+    &[RawWindowHandleKind::Windows, RawWindowHandleKind::WinRT];
+
+#[cfg(target_arch = "wasm32")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[
+    RawWindowHandleKind::Web,
+    RawWindowHandleKind::WebCanvas,
+    RawWindowHandleKind::WebOffscreenCanvas,
+];
+
+#[cfg(target_os = "android")]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[RawWindowHandleKind::Android];
+
+#[cfg(not(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "redox",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "windows",
+    target_arch = "wasm32",
+    target_os = "android"
+)))]
+const SUPPORTED_KINDS: &[RawWindowHandleKind] = &[];
+
+/// The error returned when converting a [`RawWindowHandle`] into a platform handle of another kind.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WrongHandleKind {
+    /// The kind of handle the conversion expected.
+    pub expected: RawWindowHandleKind,
+    /// The kind of handle that was passed in.
+    pub found: RawWindowHandleKind,
+}
+
+impl fmt::Display for WrongHandleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {} handle, found a {} handle",
+            self.expected, self.found
+        )
+    }
+}
+
+impl core::error::Error for WrongHandleKind {}
@@ -12,79 +12,134 @@
 //! be used along with the struct update syntax to construct it. See each specific struct for
 //! examples.
 //!
+//! ## Forwarding impls
+//!
+//! The handle traits are implemented for references, and with the `alloc` feature for `Box`,
+//! `Rc` and `Arc`, so that consumers can accept e.g. an `Arc<Window>` or a
+//! `&dyn HasRawWindowHandle` directly. These impls forward to the inner value unchanged,
+//! including its errors:
+//!
+//! ```
+//! # use raw_window_handle::{HandleError, HasRawDisplayHandle, HasRawWindowHandle, HasWindowHandle};
+//! # use raw_window_handle::{RawDisplayHandle, RawWindowHandle, WindowHandle};
+//! # use raw_window_handle::unix::{XlibDisplayHandle, XlibHandle};
+//! /// A window whose handles are currently unavailable.
+//! struct Suspended;
+//!
+//! fn raw() -> RawWindowHandle {
+//!     RawWindowHandle::Xlib(XlibHandle::empty())
+//! }
+//!
+//! fn display() -> RawDisplayHandle {
+//!     RawDisplayHandle::Xlib(XlibDisplayHandle::empty())
+//! }
+//!
+//! unsafe impl HasRawWindowHandle for Suspended {
+//!     fn raw_window_handle(&self) -> RawWindowHandle {
+//!         raw()
+//!     }
+//!
+//!     fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+//!         Err(HandleError::Unavailable)
+//!     }
+//! }
+//!
+//! unsafe impl HasRawDisplayHandle for Suspended {
+//!     fn raw_display_handle(&self) -> RawDisplayHandle {
+//!         display()
+//!     }
+//! }
+//!
+//! impl HasWindowHandle for Suspended {
+//!     fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+//!         Err(HandleError::Destroyed)
+//!     }
+//! }
+//!
+//! fn check_raw<T: HasRawWindowHandle + ?Sized>(window: &T) {
+//!     assert_eq!(window.raw_window_handle(), raw());
+//!     assert_eq!(window.try_raw_window_handle(), Err(HandleError::Unavailable));
+//! }
+//!
+//! fn check<T: HasRawWindowHandle + HasRawDisplayHandle + HasWindowHandle>(window: T) {
+//!     check_raw(&window);
+//!     assert_eq!(window.raw_display_handle(), display());
+//!     assert_eq!(window.window_handle().err(), Some(HandleError::Destroyed));
+//! }
+//!
+//! check(&Suspended);
+//! check(&&Suspended);
+//! check(&mut Suspended);
+//! check_raw(&Suspended as &dyn HasRawWindowHandle);
+//! #[cfg(feature = "alloc")]
+//! {
+//!     extern crate alloc;
+//!     use alloc::{boxed::Box, rc::Rc, sync::Arc};
+//!
+//!     check(Box::new(Suspended));
+//!     check(Rc::new(Suspended));
+//!     check(Arc::new(Suspended));
+//!     check_raw(&(Box::new(Suspended) as Box<dyn HasRawWindowHandle>));
+//! }
+//! ```
+//!
+//! ## Platform availability
+//!
+//! All platform handle types and all variants of [`RawWindowHandle`] and [`RawDisplayHandle`] are
+//! available on every target, so that code matching on them compiles everywhere. Only items that
+//! call into platform APIs are restricted to the targets that provide them.
+//!
 #![cfg_attr(feature = "nightly-docs", feature(doc_cfg))]
 #![no_std]
 
-#[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "android")))]
-#[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "android"))]
+#[cfg(any(feature = "alloc", feature = "web-sys"))]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod borrowed;
+mod export;
+mod geometry;
+mod kind;
+mod observer;
+mod owned;
+mod portal;
+#[cfg(feature = "serde")]
+mod serialize;
+mod thread;
+
+use core::fmt;
+
+#[allow(deprecated)]
+pub use borrowed::TrustedWindowHandle;
+pub use borrowed::{Active, ActiveHandle, HasWindowHandle, WindowHandle};
+pub use export::{ExportedWindowHandle, ParseExportedWindowHandleError};
+pub use geometry::{HasSurfaceGeometry, ScaleFactor, SurfaceGeometry};
+pub use kind::{RawWindowHandleKind, WrongHandleKind};
+#[cfg(feature = "stream")]
+pub use observer::{HandleEvent, HandleEvents, NextEvent};
+pub use observer::{HandleObserver, ObserverId};
+#[cfg(feature = "alloc")]
+pub use observer::{HandleObservers, ObservableWindowHandle};
+pub use owned::OwnedWindowHandle;
+pub use portal::{ParsePortalParentWindowError, PortalParentWindow};
+#[cfg(feature = "derive")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "derive")))]
+pub use raw_window_handle_derive::{HasRawDisplayHandle, HasRawWindowHandle, HasWindowHandle};
+pub use thread::{CrossThreadUse, MainThreadMarker, SendableWindowHandle};
+
 pub mod android;
-#[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "ios")))]
-#[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "ios"))]
 pub mod ios;
-#[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "macos")))]
-#[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "macos"))]
+pub mod linux;
 pub mod macos;
-#[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "redox")))]
-#[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "redox"))]
 pub mod redox;
-#[cfg_attr(
-    feature = "nightly-docs",
-    doc(cfg(any(
-        target_os = "linux",
-        target_os = "dragonfly",
-        target_os = "freebsd",
-        target_os = "netbsd",
-        target_os = "openbsd",
-        target_os = "solaris"
-    )))
-)]
-#[cfg_attr(
-    not(feature = "nightly-docs"),
-    cfg(any(
-        target_os = "linux",
-        target_os = "dragonfly",
-        target_os = "freebsd",
-        target_os = "netbsd",
-        target_os = "openbsd",
-        target_os = "solaris"
-    ))
-)]
 pub mod unix;
-#[cfg_attr(feature = "nightly-docs", doc(cfg(target_arch = "wasm32")))]
-#[cfg_attr(not(feature = "nightly-docs"), cfg(target_arch = "wasm32"))]
 pub mod web;
-#[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "windows")))]
-#[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "windows"))]
 pub mod windows;
 
-mod platform {
-    #[cfg(target_os = "android")]
-    pub use crate::android::*;
-    #[cfg(target_os = "macos")]
-    pub use crate::macos::*;
-    #[cfg(target_os = "redox")]
-    pub use crate::redox::*;
-    #[cfg(any(
-        target_os = "linux",
-        target_os = "dragonfly",
-        target_os = "freebsd",
-        target_os = "netbsd",
-        target_os = "openbsd",
-        target_os = "solaris",
-    ))]
-    pub use crate::unix::*;
-    #[cfg(target_os = "windows")]
-    pub use crate::windows::*;
-    // mod platform;
-    #[cfg(target_os = "ios")]
-    pub use crate::ios::*;
-    #[cfg(target_arch = "wasm32")]
-    pub use crate::web::*;
-}
-
 /// Window that wraps around a raw window handle.
 ///
-/// # Safety guarantees
+/// # Safety
 ///
 /// Users can safely assume that non-`null`/`0` fields are valid handles, and it is up to the
 /// implementer of this trait to ensure that condition is upheld.
@@ -96,136 +151,399 @@ mod platform {
 ///
 /// The exact handles returned by `raw_window_handle` must remain consistent between multiple calls
 /// to `raw_window_handle` as long as not indicated otherwise by platform specific events.
+///
+/// The handles are only guaranteed to be valid for as long as the implementer is alive. Prefer
+/// [`HasWindowHandle`], which ties the returned handle to the lifetime of the implementer.
+///
+/// # Failure
+///
+/// Some implementers can't always provide a valid handle, e.g. an Android window while the
+/// application is suspended, or a headless window. Such implementers must override
+/// `try_raw_window_handle` to return an error in that state. While `try_raw_window_handle` returns
+/// an error, `raw_window_handle` must return a handle with all fields empty, and users must not
+/// rely on any handle previously obtained from the implementer.
 pub unsafe trait HasRawWindowHandle {
     fn raw_window_handle(&self) -> RawWindowHandle;
+
+    /// Get the raw window handle, or the reason why no valid handle is available.
+    ///
+    /// The default implementation always succeeds with the value of `raw_window_handle`.
+    fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        Ok(self.raw_window_handle())
+    }
 }
 
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RawWindowHandle {
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "ios")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "ios"))]
     IOS(ios::IOSHandle),
-
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "macos")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "macos"))]
     MacOS(macos::MacOSHandle),
-
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "redox")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "redox"))]
-    Redox(redox::RedoxHandle),
-
-    #[cfg_attr(
-        feature = "nightly-docs",
-        doc(cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-            target_os = "solaris"
-        )))
-    )]
-    #[cfg_attr(
-        not(feature = "nightly-docs"),
-        cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-            target_os = "solaris"
-        ))
-    )]
+    Orbital(redox::OrbitalHandle),
     Xlib(unix::XlibHandle),
-
-    #[cfg_attr(
-        feature = "nightly-docs",
-        doc(cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-            target_os = "solaris"
-        )))
-    )]
-    #[cfg_attr(
-        not(feature = "nightly-docs"),
-        cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd",
-            target_os = "solaris"
-        ))
-    )]
     Xcb(unix::XcbHandle),
-
-    #[cfg_attr(
-        feature = "nightly-docs",
-        doc(cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd"
-        )))
-    )]
-    #[cfg_attr(
-        not(feature = "nightly-docs"),
-        cfg(any(
-            target_os = "linux",
-            target_os = "dragonfly",
-            target_os = "freebsd",
-            target_os = "netbsd",
-            target_os = "openbsd"
-        ))
-    )]
     Wayland(unix::WaylandHandle),
-
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "windows")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "windows"))]
     Windows(windows::WindowsHandle),
-
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "windows")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "windows"))]
     WinRT(windows::WinRTHandle),
-
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_arch = "wasm32")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_arch = "wasm32"))]
     Web(web::WebHandle),
-
-    #[cfg_attr(feature = "nightly-docs", doc(cfg(target_os = "android")))]
-    #[cfg_attr(not(feature = "nightly-docs"), cfg(target_os = "android"))]
+    WebCanvas(web::WebCanvasHandle),
+    WebOffscreenCanvas(web::WebOffscreenCanvasHandle),
     Android(android::AndroidHandle),
+    Drm(linux::DrmHandle),
+    Gbm(linux::GbmHandle),
 }
 
-/// This wraps a [`RawWindowHandle`] to give it a [`HasRawWindowHandle`] impl.
+unsafe impl<T: HasRawWindowHandle + ?Sized> HasRawWindowHandle for &T {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        (**self).raw_window_handle()
+    }
+
+    fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        (**self).try_raw_window_handle()
+    }
+}
+
+unsafe impl<T: HasRawWindowHandle + ?Sized> HasRawWindowHandle for &mut T {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        (**self).raw_window_handle()
+    }
+
+    fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        (**self).try_raw_window_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+unsafe impl<T: HasRawWindowHandle + ?Sized> HasRawWindowHandle for alloc::boxed::Box<T> {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        (**self).raw_window_handle()
+    }
+
+    fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        (**self).try_raw_window_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+unsafe impl<T: HasRawWindowHandle + ?Sized> HasRawWindowHandle for alloc::rc::Rc<T> {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        (**self).raw_window_handle()
+    }
+
+    fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        (**self).try_raw_window_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+unsafe impl<T: HasRawWindowHandle + ?Sized> HasRawWindowHandle for alloc::sync::Arc<T> {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        (**self).raw_window_handle()
+    }
+
+    fn try_raw_window_handle(&self) -> Result<RawWindowHandle, HandleError> {
+        (**self).try_raw_window_handle()
+    }
+}
+
+/// The role of a window relative to its parent.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowRole {
+    /// A top-level window. If it has a parent, it is transient for that parent, e.g. a dialog.
+    Toplevel,
+    /// A short-lived window positioned relative to its parent, e.g. a popup menu or a tooltip.
+    Popup,
+    /// A window placed inside its parent, e.g. an X11 child window or a Wayland subsurface.
+    Child,
+    /// A window embedded into a parent owned by another client or process, e.g. a plugin UI.
+    Embedded,
+}
+
+/// Window that knows its parent window and its role relative to that parent.
+///
+/// The default implementations read the `parent` and `role` fields of the Xlib, Xcb and Wayland
+/// handles returned by `raw_window_handle`. Providers on other platforms override them.
 ///
-/// The `HasRawWindowHandle` trait must be an `unsafe` trait because *other*
-/// unsafe code is going to rely on it to provide accurate window handle info.
-/// Since `RawWindowHandle` is an enum and enum fields are public, anyone could
-/// make any random `RawWindowHandle` value in safe code.
+/// ## Example
+/// ```
+/// # use raw_window_handle::{HasRawWindowHandle, HasRawWindowRelationship, RawWindowHandle, WindowRole};
+/// # use core::num::NonZeroU64;
+/// # use raw_window_handle::unix::XlibHandle;
+/// struct Popup;
 ///
-/// The solution is that you assert that you're trusting a particular handle
-/// value by (unsafely) placing it within this wrapper struct.
-pub struct TrustedWindowHandle {
-    raw: RawWindowHandle,
+/// unsafe impl HasRawWindowHandle for Popup {
+///     fn raw_window_handle(&self) -> RawWindowHandle {
+///         let mut handle = XlibHandle::empty();
+///         handle.window = NonZeroU64::new(2);
+///         handle.parent = NonZeroU64::new(1);
+///         handle.role = Some(WindowRole::Popup);
+///         RawWindowHandle::Xlib(handle)
+///     }
+/// }
+///
+/// unsafe impl HasRawWindowRelationship for Popup {}
+///
+/// let mut parent = XlibHandle::empty();
+/// parent.window = NonZeroU64::new(1);
+/// assert_eq!(Popup.raw_parent_window_handle(), Some(RawWindowHandle::Xlib(parent)));
+/// assert_eq!(Popup.window_role(), Some(WindowRole::Popup));
+/// ```
+///
+/// # Safety
+///
+/// The parent handle must uphold the same guarantees as the handle returned by
+/// `raw_window_handle`.
+pub unsafe trait HasRawWindowRelationship: HasRawWindowHandle {
+    /// Get the raw handle of the window's parent, or `None` if it has no parent.
+    fn raw_parent_window_handle(&self) -> Option<RawWindowHandle> {
+        self.raw_window_handle().parent()
+    }
+
+    /// Get the role of the window, or `None` if it is unknown.
+    fn window_role(&self) -> Option<WindowRole> {
+        self.raw_window_handle().role()
+    }
 }
-impl TrustedWindowHandle {
-    /// Assert that the `RawWindowHandle` value can be trusted.
+
+impl RawWindowHandle {
+    /// Get the handle of the parent window recorded in this handle, if any.
+    ///
+    /// The parent handle shares the connection of this handle. Only Xlib, Xcb and Wayland handles
+    /// record their parent; `None` is returned for other platforms.
+    pub fn parent(&self) -> Option<RawWindowHandle> {
+        match self {
+            RawWindowHandle::Xlib(handle) if handle.parent.is_some() => {
+                let mut parent = unix::XlibHandle::empty();
+                parent.window = handle.parent;
+                parent.display = handle.display;
+                Some(RawWindowHandle::Xlib(parent))
+            }
+            RawWindowHandle::Xcb(handle) if handle.parent.is_some() => {
+                let mut parent = unix::XcbHandle::empty();
+                parent.window = handle.parent;
+                parent.connection = handle.connection;
+                Some(RawWindowHandle::Xcb(parent))
+            }
+            RawWindowHandle::Wayland(handle) if handle.parent.is_some() => {
+                let mut parent = unix::WaylandHandle::empty();
+                parent.surface = handle.parent;
+                parent.display = handle.display;
+                Some(RawWindowHandle::Wayland(parent))
+            }
+            _ => None,
+        }
+    }
+
+    /// Check whether the fields required to use this handle are present.
+    ///
+    /// These are the window and its connection for Xlib, Xcb and Wayland, the view for AppKit
+    /// and UIKit, and the native window or surface for all other platforms.
     ///
-    /// ## Safety
-    /// If the value violates any of the safety outlines given in the
-    /// [`HasRawWindowHandle`] trait this can lead to UB.
-    pub const unsafe fn new(raw: RawWindowHandle) -> Self {
-        Self { raw }
+    /// ```
+    /// # use raw_window_handle::RawWindowHandle;
+    /// # use raw_window_handle::unix::XlibHandle;
+    /// let mut handle = XlibHandle::empty();
+    /// assert!(!RawWindowHandle::Xlib(handle).is_complete());
+    /// handle = XlibHandle::new(0x3a00007, 0x1000 as *mut _).unwrap();
+    /// assert!(RawWindowHandle::Xlib(handle).is_complete());
+    /// ```
+    pub fn is_complete(&self) -> bool {
+        match self {
+            RawWindowHandle::IOS(handle) => handle.ui_view.is_some(),
+            RawWindowHandle::MacOS(handle) => handle.ns_view.is_some(),
+            RawWindowHandle::Orbital(handle) => handle.window.is_some(),
+            RawWindowHandle::Xlib(handle) => handle.window.is_some() && handle.display.is_some(),
+            RawWindowHandle::Xcb(handle) => handle.window.is_some() && handle.connection.is_some(),
+            RawWindowHandle::Wayland(handle) => {
+                handle.surface.is_some() && handle.display.is_some()
+            }
+            RawWindowHandle::Windows(handle) => handle.hwnd.is_some(),
+            RawWindowHandle::WinRT(handle) => handle.core_window.is_some(),
+            RawWindowHandle::Web(handle) => handle.id != 0,
+            RawWindowHandle::WebCanvas(handle) => handle.obj.is_some(),
+            RawWindowHandle::WebOffscreenCanvas(handle) => handle.obj.is_some(),
+            RawWindowHandle::Android(handle) => handle.a_native_window.is_some(),
+            RawWindowHandle::Drm(handle) => handle.fd >= 0 && handle.plane != 0,
+            RawWindowHandle::Gbm(handle) => handle.gbm_surface.is_some(),
+        }
+    }
+
+    /// Get the role of the window recorded in this handle, if any.
+    pub fn role(&self) -> Option<WindowRole> {
+        match self {
+            RawWindowHandle::Xlib(handle) => handle.role,
+            RawWindowHandle::Xcb(handle) => handle.role,
+            RawWindowHandle::Wayland(handle) => handle.role,
+            _ => None,
+        }
     }
 }
-unsafe impl HasRawWindowHandle for TrustedWindowHandle {
-    fn raw_window_handle(&self) -> RawWindowHandle {
-        self.raw
+
+/// Display that wraps around a raw display handle.
+///
+/// # Safety
+///
+/// Users can safely assume that non-`null`/`0` fields are valid handles, and it is up to the
+/// implementer of this trait to ensure that condition is upheld.
+///
+/// Display handles are available independently of any window, so that graphics libraries can set
+/// up global state (e.g. a Vulkan instance or an EGL display) before the first window is created.
+///
+/// The exact handles returned by `raw_display_handle` must remain consistent between multiple
+/// calls to `raw_display_handle` as long as not indicated otherwise by platform specific events.
+pub unsafe trait HasRawDisplayHandle {
+    fn raw_display_handle(&self) -> RawDisplayHandle;
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawDisplayHandle {
+    UiKit(ios::UiKitDisplayHandle),
+    AppKit(macos::AppKitDisplayHandle),
+    Orbital(redox::OrbitalDisplayHandle),
+    Xlib(unix::XlibDisplayHandle),
+    Xcb(unix::XcbDisplayHandle),
+    Wayland(unix::WaylandDisplayHandle),
+    Windows(windows::WindowsDisplayHandle),
+    Web(web::WebDisplayHandle),
+    Android(android::AndroidDisplayHandle),
+    Drm(linux::DrmDisplayHandle),
+    Gbm(linux::GbmDisplayHandle),
+}
+
+unsafe impl<T: HasRawDisplayHandle + ?Sized> HasRawDisplayHandle for &T {
+    fn raw_display_handle(&self) -> RawDisplayHandle {
+        (**self).raw_display_handle()
+    }
+}
+
+unsafe impl<T: HasRawDisplayHandle + ?Sized> HasRawDisplayHandle for &mut T {
+    fn raw_display_handle(&self) -> RawDisplayHandle {
+        (**self).raw_display_handle()
     }
 }
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+unsafe impl<T: HasRawDisplayHandle + ?Sized> HasRawDisplayHandle for alloc::boxed::Box<T> {
+    fn raw_display_handle(&self) -> RawDisplayHandle {
+        (**self).raw_display_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+unsafe impl<T: HasRawDisplayHandle + ?Sized> HasRawDisplayHandle for alloc::rc::Rc<T> {
+    fn raw_display_handle(&self) -> RawDisplayHandle {
+        (**self).raw_display_handle()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+unsafe impl<T: HasRawDisplayHandle + ?Sized> HasRawDisplayHandle for alloc::sync::Arc<T> {
+    fn raw_display_handle(&self) -> RawDisplayHandle {
+        (**self).raw_display_handle()
+    }
+}
+
+/// Monitor that wraps around a raw monitor handle.
+///
+/// This is implemented by windows to report the monitor(s) they are currently shown on, and may
+/// also be implemented by a windowing library's own monitor type.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{HasRawMonitorHandle, RawMonitorHandle};
+/// # use raw_window_handle::unix::XcbMonitorHandle;
+/// struct Window {
+///     monitors: [XcbMonitorHandle; 2],
+/// }
+///
+/// unsafe impl HasRawMonitorHandle for Window {
+///     fn raw_monitor_handle(&self) -> RawMonitorHandle {
+///         RawMonitorHandle::Xcb(self.monitors[0])
+///     }
+///
+///     fn for_each_raw_monitor_handle(&self, f: &mut dyn FnMut(RawMonitorHandle)) {
+///         self.monitors.iter().for_each(|&monitor| f(RawMonitorHandle::Xcb(monitor)));
+///     }
+/// }
+///
+/// let mut left = XcbMonitorHandle::empty();
+/// left.output = 1;
+/// let mut right = XcbMonitorHandle::empty();
+/// right.output = 2;
+/// let window = Window { monitors: [left, right] };
+///
+/// let mut count = 0;
+/// window.for_each_raw_monitor_handle(&mut |_| count += 1);
+/// assert_eq!(count, 2);
+/// assert_eq!(window.raw_monitor_handle(), RawMonitorHandle::Xcb(left));
+/// ```
+///
+/// # Safety
+///
+/// Users can safely assume that non-`null`/`0` fields are valid handles, and it is up to the
+/// implementer of this trait to ensure that condition is upheld.
+///
+/// Monitor handles may change whenever the window is moved or the monitor configuration changes.
+/// They are only guaranteed to be valid until the implementer's next platform event is processed.
+pub unsafe trait HasRawMonitorHandle {
+    /// Get the monitor the window is currently shown on.
+    ///
+    /// If the window spans several monitors, this is the one that contains most of it.
+    fn raw_monitor_handle(&self) -> RawMonitorHandle;
+
+    /// Call `f` with every monitor the window is currently shown on.
+    ///
+    /// The default implementation only reports `raw_monitor_handle`.
+    fn for_each_raw_monitor_handle(&self, f: &mut dyn FnMut(RawMonitorHandle)) {
+        f(self.raw_monitor_handle())
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawMonitorHandle {
+    AppKit(macos::AppKitMonitorHandle),
+    Xlib(unix::XlibMonitorHandle),
+    Xcb(unix::XcbMonitorHandle),
+    Wayland(unix::WaylandMonitorHandle),
+    Windows(windows::WindowsMonitorHandle),
+}
+
+/// The error type returned when a handle can't be retrieved.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandleError {
+    /// The handle is not available right now, but may become available later, e.g. because the
+    /// application is suspended.
+    Unavailable,
+    /// The underlying windowing system can't provide a handle of this kind at all, e.g. because it
+    /// is running headless.
+    NotSupported,
+    /// The window or display that the handle referred to has been destroyed.
+    Destroyed,
+    /// The handle may only be used on another thread, e.g. the main thread.
+    WrongThread,
+}
+
+impl fmt::Display for HandleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandleError::Unavailable => f.write_str("the handle is not available right now"),
+            HandleError::NotSupported => {
+                f.write_str("the windowing system does not support this handle")
+            }
+            HandleError::Destroyed => f.write_str("the window or display has been destroyed"),
+            HandleError::WrongThread => f.write_str("the handle may not be used on this thread"),
+        }
+    }
+}
+
+impl core::error::Error for HandleError {}
@@ -0,0 +1,121 @@
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use cty::c_int;
+
+/// Raw window handle for the Linux kernel mode setting/direct rendering manager.
+///
+/// This is used for rendering directly to a display plane, without any compositor.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::RawWindowHandle;
+/// # use raw_window_handle::linux::DrmHandle;
+/// let mut handle = DrmHandle::empty();
+/// handle.fd = 3;
+/// handle.plane = 31;
+/// handle.connector = 77;
+/// handle.crtc = 42;
+///
+/// match RawWindowHandle::Drm(handle) {
+///     RawWindowHandle::Drm(round_trip) => assert_eq!(round_trip, handle),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DrmHandle {
+    /// The file descriptor of the DRM device, or `-1`.
+    pub fd: c_int,
+    /// The ID of the plane to render to.
+    pub plane: u32,
+    /// The ID of the connector the plane is shown on.
+    pub connector: u32,
+    /// The ID of the CRTC driving the connector.
+    pub crtc: u32,
+}
+
+/// Raw window handle for the generic buffer manager.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::linux::GbmHandle;
+/// let handle = GbmHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GbmHandle {
+    /// A pointer to a `gbm_surface`.
+    pub gbm_surface: Option<NonNull<c_void>>,
+    /// A pointer to the `gbm_device` the surface was created on.
+    pub gbm_device: Option<NonNull<c_void>>,
+}
+
+/// Raw display handle for the Linux kernel mode setting/direct rendering manager.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::RawDisplayHandle;
+/// # use raw_window_handle::linux::DrmDisplayHandle;
+/// let mut handle = DrmDisplayHandle::empty();
+/// handle.fd = 3;
+///
+/// match RawDisplayHandle::Drm(handle) {
+///     RawDisplayHandle::Drm(round_trip) => assert_eq!(round_trip, handle),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DrmDisplayHandle {
+    /// The file descriptor of the DRM device, or `-1`.
+    pub fd: c_int,
+}
+
+/// Raw display handle for the generic buffer manager.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::linux::GbmDisplayHandle;
+/// let handle = GbmDisplayHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GbmDisplayHandle {
+    /// A pointer to a `gbm_device`.
+    pub gbm_device: Option<NonNull<c_void>>,
+}
+
+impl DrmHandle {
+    pub fn empty() -> DrmHandle {
+        DrmHandle {
+            fd: -1,
+            plane: 0,
+            connector: 0,
+            crtc: 0,
+        }
+    }
+}
+
+impl GbmHandle {
+    pub fn empty() -> GbmHandle {
+        GbmHandle {
+            gbm_surface: None,
+            gbm_device: None,
+        }
+    }
+}
+
+impl DrmDisplayHandle {
+    pub fn empty() -> DrmDisplayHandle {
+        DrmDisplayHandle { fd: -1 }
+    }
+}
+
+impl GbmDisplayHandle {
+    pub fn empty() -> GbmDisplayHandle {
+        GbmDisplayHandle { gbm_device: None }
+    }
+}
@@ -3,6 +3,9 @@ use core::ptr::NonNull;
 
 /// Raw window handle for macOS.
 ///
+/// The objects referred to by this handle may only be used on the main thread, see
+/// [`MainThreadMarker`](crate::MainThreadMarker).
+///
 /// ## Construction
 /// ```
 /// # use raw_window_handle::macos::MacOSHandle;
@@ -25,3 +28,41 @@ impl MacOSHandle {
         }
     }
 }
+
+/// Raw display handle for AppKit.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::macos::AppKitDisplayHandle;
+/// let handle = AppKitDisplayHandle::empty();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AppKitDisplayHandle {}
+
+impl AppKitDisplayHandle {
+    pub fn empty() -> AppKitDisplayHandle {
+        AppKitDisplayHandle {}
+    }
+}
+
+/// Raw monitor handle for AppKit.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::macos::AppKitMonitorHandle;
+/// let mut handle = AppKitMonitorHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AppKitMonitorHandle {
+    /// A pointer to an `NSScreen`.
+    pub ns_screen: Option<NonNull<c_void>>,
+}
+
+impl AppKitMonitorHandle {
+    pub fn empty() -> AppKitMonitorHandle {
+        AppKitMonitorHandle { ns_screen: None }
+    }
+}
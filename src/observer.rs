@@ -0,0 +1,356 @@
+use crate::RawWindowHandle;
+
+#[cfg(feature = "alloc")]
+use crate::HasRawWindowHandle;
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, vec::Vec};
+
+/// Receives notifications when a window handle stops being valid.
+///
+/// The handles returned by [`HasRawWindowHandle`](crate::HasRawWindowHandle) stay the same as long as not indicated otherwise
+/// by platform specific events. Providers use this trait to deliver that indication, so that
+/// consumers can e.g. drop and recreate their swapchains when the underlying `wl_surface`, X
+/// window or native window changes.
+pub trait HandleObserver {
+    /// The handle has been invalidated and no replacement is available (yet).
+    ///
+    /// `handle` must not be used after this call returns.
+    fn on_invalidated(&self, handle: RawWindowHandle);
+
+    /// The handle has been replaced by a new one.
+    ///
+    /// `old` must not be used after this call returns.
+    fn on_replaced(&self, old: RawWindowHandle, new: RawWindowHandle);
+}
+
+/// Identifies an observer registered with an `ObservableWindowHandle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+/// A window that notifies registered observers when its handle changes.
+///
+/// ## Example
+/// ```
+/// # use core::cell::{Cell, RefCell};
+/// # use std::rc::Rc;
+/// # use raw_window_handle::{
+/// #     HandleObserver, HandleObservers, HasRawWindowHandle, ObservableWindowHandle, ObserverId,
+/// #     RawWindowHandle,
+/// # };
+/// # use raw_window_handle::android::AndroidHandle;
+/// struct Window {
+///     raw: Cell<RawWindowHandle>,
+///     observers: RefCell<HandleObservers>,
+/// }
+///
+/// unsafe impl HasRawWindowHandle for Window {
+///     fn raw_window_handle(&self) -> RawWindowHandle {
+///         self.raw.get()
+///     }
+/// }
+///
+/// impl ObservableWindowHandle for Window {
+///     fn register_observer(&self, observer: Rc<dyn HandleObserver>) -> ObserverId {
+///         self.observers.borrow_mut().register(observer)
+///     }
+///
+///     fn unregister_observer(&self, id: ObserverId) -> bool {
+///         self.observers.borrow_mut().unregister(id)
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Swapchain {
+///     recreated: Cell<u32>,
+/// }
+///
+/// impl HandleObserver for Swapchain {
+///     fn on_invalidated(&self, _handle: RawWindowHandle) {}
+///
+///     fn on_replaced(&self, _old: RawWindowHandle, _new: RawWindowHandle) {
+///         self.recreated.set(self.recreated.get() + 1);
+///     }
+/// }
+///
+/// let window = Window {
+///     raw: Cell::new(RawWindowHandle::Android(AndroidHandle::empty())),
+///     observers: RefCell::new(HandleObservers::new()),
+/// };
+/// let swapchain = Rc::new(Swapchain::default());
+/// let id = window.register_observer(swapchain.clone());
+///
+/// // The provider replaces the underlying surface.
+/// let old = window.raw.replace(RawWindowHandle::Android(AndroidHandle::empty()));
+/// window.observers.borrow().notify_replaced(old, window.raw_window_handle());
+/// assert_eq!(swapchain.recreated.get(), 1);
+///
+/// assert!(window.unregister_observer(id));
+/// window.observers.borrow().notify_replaced(old, window.raw_window_handle());
+/// assert_eq!(swapchain.recreated.get(), 1);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+pub trait ObservableWindowHandle: HasRawWindowHandle {
+    /// Register an observer that is notified whenever the window's handle changes.
+    fn register_observer(&self, observer: Rc<dyn HandleObserver>) -> ObserverId;
+
+    /// Unregister a previously registered observer.
+    ///
+    /// Returns `false` if no observer with this id is registered.
+    fn unregister_observer(&self, id: ObserverId) -> bool;
+}
+
+/// A list of observers, for implementing [`ObservableWindowHandle`].
+///
+/// Observers are notified in the order they were registered. Providers usually keep this in a
+/// `RefCell`; they must not hold a mutable borrow of it while notifying, and observers must not
+/// register or unregister themselves from within their callbacks.
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+#[derive(Default)]
+pub struct HandleObservers {
+    next_id: u64,
+    observers: Vec<(ObserverId, Rc<dyn HandleObserver>)>,
+}
+
+#[cfg(feature = "alloc")]
+impl HandleObservers {
+    pub fn new() -> HandleObservers {
+        HandleObservers::default()
+    }
+
+    /// Add an observer to the list.
+    pub fn register(&mut self, observer: Rc<dyn HandleObserver>) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.observers.push((id, observer));
+        id
+    }
+
+    /// Remove an observer from the list, returning whether it was registered.
+    pub fn unregister(&mut self, id: ObserverId) -> bool {
+        let len = self.observers.len();
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+        self.observers.len() != len
+    }
+
+    /// Call [`HandleObserver::on_invalidated`] on every registered observer.
+    pub fn notify_invalidated(&self, handle: RawWindowHandle) {
+        for (_, observer) in &self.observers {
+            observer.on_invalidated(handle);
+        }
+    }
+
+    /// Call [`HandleObserver::on_replaced`] on every registered observer.
+    pub fn notify_replaced(&self, old: RawWindowHandle, new: RawWindowHandle) {
+        for (_, observer) in &self.observers {
+            observer.on_replaced(old, new);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for HandleObservers {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HandleObservers")
+            .field("len", &self.observers.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "stream")]
+pub use self::stream::{HandleEvent, HandleEvents, NextEvent};
+
+#[cfg(feature = "stream")]
+mod stream {
+    use core::cell::RefCell;
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, Waker};
+
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+
+    use super::HandleObserver;
+    use crate::RawWindowHandle;
+
+    /// A notification delivered through [`HandleEvents`].
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "stream")))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum HandleEvent {
+        /// See [`HandleObserver::on_invalidated`].
+        Invalidated(RawWindowHandle),
+        /// See [`HandleObserver::on_replaced`].
+        Replaced {
+            old: RawWindowHandle,
+            new: RawWindowHandle,
+        },
+    }
+
+    #[derive(Default)]
+    struct Shared {
+        events: VecDeque<HandleEvent>,
+        waker: Option<Waker>,
+    }
+
+    struct Observer {
+        shared: Rc<RefCell<Shared>>,
+    }
+
+    impl Observer {
+        fn push(&self, event: HandleEvent) {
+            let waker = {
+                let mut shared = self.shared.borrow_mut();
+                shared.events.push_back(event);
+                shared.waker.take()
+            };
+            // Wake without holding the borrow, the executor may poll the stream right away.
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    impl HandleObserver for Observer {
+        fn on_invalidated(&self, handle: RawWindowHandle) {
+            self.push(HandleEvent::Invalidated(handle));
+        }
+
+        fn on_replaced(&self, old: RawWindowHandle, new: RawWindowHandle) {
+            self.push(HandleEvent::Replaced { old, new });
+        }
+    }
+
+    impl Drop for Observer {
+        fn drop(&mut self) {
+            let waker = self.shared.borrow_mut().waker.take();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// An asynchronous stream of handle notifications.
+    ///
+    /// [`HandleEvents::observer`] creates observers that feed this stream. Once every such observer
+    /// has been dropped and all queued events have been received, the stream ends.
+    ///
+    /// `HandleEvents` implements `futures_core::Stream`, so it works with the stream combinators of
+    /// the `futures` ecosystem.
+    ///
+    /// ## Example
+    /// ```
+    /// # use core::pin::Pin;
+    /// # use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    /// # use raw_window_handle::{HandleEvent, HandleEvents, RawWindowHandle};
+    /// # use raw_window_handle::android::AndroidHandle;
+    /// # fn noop_raw_waker() -> RawWaker {
+    /// #     fn clone(_: *const ()) -> RawWaker {
+    /// #         noop_raw_waker()
+    /// #     }
+    /// #     fn noop(_: *const ()) {}
+    /// #     static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    /// #     RawWaker::new(core::ptr::null(), &VTABLE)
+    /// # }
+    /// # let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    /// let mut events = HandleEvents::new();
+    /// let observer = events.observer();
+    /// let mut cx = Context::from_waker(&waker);
+    ///
+    /// assert_eq!(Pin::new(&mut events).poll_next(&mut cx), Poll::Pending);
+    ///
+    /// let handle = RawWindowHandle::Android(AndroidHandle::empty());
+    /// observer.on_invalidated(handle);
+    /// assert_eq!(
+    ///     Pin::new(&mut events).poll_next(&mut cx),
+    ///     Poll::Ready(Some(HandleEvent::Invalidated(handle))),
+    /// );
+    ///
+    /// drop(observer);
+    /// assert_eq!(Pin::new(&mut events).poll_next(&mut cx), Poll::Ready(None));
+    ///
+    /// fn assert_stream(_: &impl futures_core::stream::FusedStream<Item = HandleEvent>) {}
+    /// assert_stream(&events);
+    /// ```
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "stream")))]
+    pub struct HandleEvents {
+        shared: Rc<RefCell<Shared>>,
+    }
+
+    impl HandleEvents {
+        pub fn new() -> HandleEvents {
+            HandleEvents {
+                shared: Rc::new(RefCell::new(Shared::default())),
+            }
+        }
+
+        /// Create an observer that feeds this stream, for registering with a window.
+        pub fn observer(&self) -> Rc<dyn HandleObserver> {
+            Rc::new(Observer {
+                shared: self.shared.clone(),
+            })
+        }
+
+        /// Attempt to pull out the next notification.
+        pub fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<HandleEvent>> {
+            let mut shared = self.shared.borrow_mut();
+            if let Some(event) = shared.events.pop_front() {
+                Poll::Ready(Some(event))
+            } else if Rc::strong_count(&self.shared) == 1 {
+                Poll::Ready(None)
+            } else {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+
+        /// A future resolving to the next notification, or `None` once the stream has ended.
+        pub fn next_event(&mut self) -> NextEvent<'_> {
+            NextEvent { events: self }
+        }
+    }
+
+    impl futures_core::Stream for HandleEvents {
+        type Item = HandleEvent;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<HandleEvent>> {
+            HandleEvents::poll_next(self, cx)
+        }
+    }
+
+    impl futures_core::FusedStream for HandleEvents {
+        fn is_terminated(&self) -> bool {
+            let shared = self.shared.borrow();
+            shared.events.is_empty() && Rc::strong_count(&self.shared) == 1
+        }
+    }
+
+    impl Default for HandleEvents {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl core::fmt::Debug for HandleEvents {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("HandleEvents")
+                .field("pending", &self.shared.borrow().events.len())
+                .finish()
+        }
+    }
+
+    /// Future returned by [`HandleEvents::next_event`].
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "stream")))]
+    #[derive(Debug)]
+    pub struct NextEvent<'a> {
+        events: &'a mut HandleEvents,
+    }
+
+    impl Future for NextEvent<'_> {
+        type Output = Option<HandleEvent>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut *self.events).poll_next(cx)
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use crate::{
+    ActiveHandle, HandleError, HasRawWindowHandle, HasWindowHandle, RawWindowHandle, WindowHandle,
+};
+
+enum Release {
+    Fn(unsafe fn(RawWindowHandle)),
+    #[cfg(feature = "alloc")]
+    Closure(Box<dyn FnOnce(RawWindowHandle)>),
+}
+
+/// A window handle that owns the window it refers to.
+///
+/// The release callback runs when the `OwnedWindowHandle` is dropped, e.g. to destroy an X11
+/// window or a `wl_surface` that was created by foreign code.
+///
+/// ## Construction
+/// ```
+/// # use core::sync::atomic::{AtomicBool, Ordering};
+/// # use raw_window_handle::{HasRawWindowHandle, OwnedWindowHandle, RawWindowHandle};
+/// # use raw_window_handle::unix::XlibHandle;
+/// static RELEASED: AtomicBool = AtomicBool::new(false);
+///
+/// unsafe fn release(_handle: RawWindowHandle) {
+///     // e.g. `XDestroyWindow(handle.display, handle.window)`
+///     RELEASED.store(true, Ordering::SeqCst);
+/// }
+///
+/// let raw = RawWindowHandle::Xlib(XlibHandle::empty());
+/// let owned = unsafe { OwnedWindowHandle::new(raw, release) };
+/// assert_eq!(owned.raw_window_handle(), raw);
+///
+/// drop(owned);
+/// assert!(RELEASED.load(Ordering::SeqCst));
+/// ```
+pub struct OwnedWindowHandle {
+    raw: RawWindowHandle,
+    release: Option<Release>,
+}
+
+impl OwnedWindowHandle {
+    /// Take ownership of a window, releasing it with `release` when dropped.
+    ///
+    /// ## Safety
+    /// The handle must uphold the guarantees given in the [`HasRawWindowHandle`] trait until it is
+    /// released, and calling `release` with it must be sound.
+    pub unsafe fn new(raw: RawWindowHandle, release: unsafe fn(RawWindowHandle)) -> Self {
+        Self {
+            raw,
+            release: Some(Release::Fn(release)),
+        }
+    }
+
+    /// Take ownership of a window, releasing it with the `release` closure when dropped.
+    ///
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use raw_window_handle::{OwnedWindowHandle, RawWindowHandle};
+    /// # use raw_window_handle::unix::WaylandHandle;
+    /// let released = Rc::new(Cell::new(false));
+    /// let raw = RawWindowHandle::Wayland(WaylandHandle::empty());
+    /// let owned = unsafe {
+    ///     let released = released.clone();
+    ///     OwnedWindowHandle::with_release(raw, move |_| released.set(true))
+    /// };
+    ///
+    /// drop(owned);
+    /// assert!(released.get());
+    /// ```
+    ///
+    /// ## Safety
+    /// The handle must uphold the guarantees given in the [`HasRawWindowHandle`] trait until it is
+    /// released.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "alloc")))]
+    pub unsafe fn with_release(
+        raw: RawWindowHandle,
+        release: impl FnOnce(RawWindowHandle) + 'static,
+    ) -> Self {
+        Self {
+            raw,
+            release: Some(Release::Closure(Box::new(release))),
+        }
+    }
+
+    /// Give up ownership of the window without releasing it.
+    ///
+    /// ```
+    /// # use raw_window_handle::{OwnedWindowHandle, RawWindowHandle};
+    /// # use raw_window_handle::unix::XlibHandle;
+    /// unsafe fn release(_handle: RawWindowHandle) {
+    ///     unreachable!();
+    /// }
+    ///
+    /// let raw = RawWindowHandle::Xlib(XlibHandle::empty());
+    /// let owned = unsafe { OwnedWindowHandle::new(raw, release) };
+    /// assert_eq!(owned.into_raw(), raw);
+    /// ```
+    pub fn into_raw(mut self) -> RawWindowHandle {
+        self.release = None;
+        self.raw
+    }
+}
+
+impl Drop for OwnedWindowHandle {
+    fn drop(&mut self) {
+        match self.release.take() {
+            Some(Release::Fn(release)) => unsafe { release(self.raw) },
+            #[cfg(feature = "alloc")]
+            Some(Release::Closure(release)) => release(self.raw),
+            None => {}
+        }
+    }
+}
+
+impl fmt::Debug for OwnedWindowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OwnedWindowHandle").field(&self.raw).finish()
+    }
+}
+
+unsafe impl HasRawWindowHandle for OwnedWindowHandle {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.raw
+    }
+}
+
+impl HasWindowHandle for OwnedWindowHandle {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        // Safety: the window is only released when `self` is dropped.
+        Ok(unsafe { WindowHandle::borrow_raw(self.raw, ActiveHandle::new_unchecked()) })
+    }
+}
@@ -0,0 +1,188 @@
+use core::fmt;
+use core::num::NonZeroU64;
+
+use crate::RawWindowHandle;
+
+/// The parent window of an xdg-desktop-portal dialog, as passed in the `parent_window` argument.
+///
+/// The [`Display`](fmt::Display) impl formats the value as the portal expects it, e.g.
+/// `x11:0x3a00007` or `wayland:<handle>`.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{PortalParentWindow, RawWindowHandle};
+/// # use raw_window_handle::unix::XlibHandle;
+/// let raw = RawWindowHandle::Xlib(XlibHandle::new(0x3a00007, 0x1000 as *mut _).unwrap());
+/// let parent = raw.to_portal_parent_window().unwrap();
+/// assert_eq!(parent.to_string(), "x11:0x3a00007");
+/// assert_eq!(PortalParentWindow::parse("x11:0x3a00007"), Ok(Some(parent)));
+///
+/// // Wayland surfaces have to be exported with `zxdg_exporter_v2` first.
+/// let parent = PortalParentWindow::Wayland("2f5a1c3e-exported");
+/// assert_eq!(parent.to_string(), "wayland:2f5a1c3e-exported");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortalParentWindow<'a> {
+    /// An X11 window ID.
+    X11(NonZeroU64),
+    /// A surface handle exported with the `zxdg_exporter_v2` protocol.
+    Wayland(&'a str),
+}
+
+impl<'a> PortalParentWindow<'a> {
+    /// Parse a `parent_window` string.
+    ///
+    /// The empty string means that the dialog has no parent, and parses as `Ok(None)`. X11 window
+    /// IDs are hexadecimal, with or without a `0x` prefix.
+    ///
+    /// ## Example
+    /// ```
+    /// # use core::num::NonZeroU64;
+    /// # use raw_window_handle::{ParsePortalParentWindowError, PortalParentWindow};
+    /// let x11 = |id| Ok(Some(PortalParentWindow::X11(NonZeroU64::new(id).unwrap())));
+    /// assert_eq!(PortalParentWindow::parse(""), Ok(None));
+    /// assert_eq!(PortalParentWindow::parse("x11:3a00007"), x11(0x3a00007));
+    /// assert_eq!(PortalParentWindow::parse("x11:0X3A00007"), x11(0x3a00007));
+    /// assert_eq!(
+    ///     PortalParentWindow::parse("wayland:abc"),
+    ///     Ok(Some(PortalParentWindow::Wayland("abc")))
+    /// );
+    ///
+    /// use ParsePortalParentWindowError::*;
+    /// for (input, err) in [
+    ///     ("x11:", EmptyToken),
+    ///     ("wayland:", EmptyToken),
+    ///     ("x11:0x", InvalidWindowId),
+    ///     ("x11:0", InvalidWindowId),
+    ///     ("x11:+1f", InvalidWindowId),
+    ///     ("x11:-1", InvalidWindowId),
+    ///     ("x11:0x 1f", InvalidWindowId),
+    ///     ("x11:10000000000000000", InvalidWindowId),
+    ///     ("x11", UnknownKind),
+    ///     ("X11:1f", UnknownKind),
+    ///     ("windows:1f", UnknownKind),
+    ///     (":1f", UnknownKind),
+    /// ] {
+    ///     assert_eq!(PortalParentWindow::parse(input), Err(err), "{:?}", input);
+    /// }
+    ///
+    /// // Arbitrary input never panics, and whatever parses formats back to an equal value.
+    /// const PREFIXES: &[&str] = &["", "x11:", "x11:0x", "wayland:", "X11:", "x11", ":"];
+    /// const ALPHABET: &[u8] = b"0123456789abcdefABCDEFxX+-: ";
+    /// let mut seed = 0x2545_f491_4f6c_dd1du64;
+    /// let mut parsed = 0;
+    /// for _ in 0..10_000 {
+    ///     seed ^= seed << 13;
+    ///     seed ^= seed >> 7;
+    ///     seed ^= seed << 17;
+    ///     let mut input = String::from(PREFIXES[seed as usize % PREFIXES.len()]);
+    ///     for i in 0..(seed >> 60) {
+    ///         input.push(ALPHABET[(seed >> (i * 4 + 3)) as usize % ALPHABET.len()] as char);
+    ///     }
+    ///     if let Ok(Some(parent)) = PortalParentWindow::parse(&input) {
+    ///         assert_eq!(PortalParentWindow::parse(&parent.to_string()), Ok(Some(parent)));
+    ///         parsed += 1;
+    ///     }
+    /// }
+    /// assert!(parsed > 1000);
+    /// for id in (0..64).map(|shift| u64::MAX >> shift) {
+    ///     let parent = PortalParentWindow::X11(NonZeroU64::new(id).unwrap());
+    ///     assert_eq!(PortalParentWindow::parse(&parent.to_string()), Ok(Some(parent)));
+    /// }
+    /// ```
+    pub fn parse(s: &'a str) -> Result<Option<Self>, ParsePortalParentWindowError> {
+        if s.is_empty() {
+            return Ok(None);
+        }
+
+        let (kind, token) = match s.find(':') {
+            Some(colon) => (&s[..colon], &s[colon + 1..]),
+            None => return Err(ParsePortalParentWindowError::UnknownKind),
+        };
+        if kind != "x11" && kind != "wayland" {
+            return Err(ParsePortalParentWindowError::UnknownKind);
+        }
+        if token.is_empty() {
+            return Err(ParsePortalParentWindowError::EmptyToken);
+        }
+
+        if kind == "wayland" {
+            return Ok(Some(PortalParentWindow::Wayland(token)));
+        }
+
+        parse_hex_id(token)
+            .map(|window| Some(PortalParentWindow::X11(window)))
+            .ok_or(ParsePortalParentWindowError::InvalidWindowId)
+    }
+}
+
+/// Parse a nonzero hexadecimal ID, with or without a `0x` prefix.
+pub(crate) fn parse_hex_id(token: &str) -> Option<NonZeroU64> {
+    let digits = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+        .unwrap_or(token);
+    // `from_str_radix` also accepts a leading `+`.
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u64::from_str_radix(digits, 16)
+        .ok()
+        .and_then(NonZeroU64::new)
+}
+
+impl fmt::Display for PortalParentWindow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortalParentWindow::X11(window) => write!(f, "x11:{:#x}", window),
+            PortalParentWindow::Wayland(handle) => write!(f, "wayland:{}", handle),
+        }
+    }
+}
+
+/// The error returned by [`PortalParentWindow::parse`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParsePortalParentWindowError {
+    /// The string doesn't start with `x11:` or `wayland:`.
+    UnknownKind,
+    /// Nothing follows the `x11:` or `wayland:` prefix.
+    EmptyToken,
+    /// The X11 window ID isn't a nonzero hexadecimal number that fits in 64 bits.
+    InvalidWindowId,
+}
+
+impl fmt::Display for ParsePortalParentWindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePortalParentWindowError::UnknownKind => {
+                f.write_str("the parent window is neither `x11:` nor `wayland:`")
+            }
+            ParsePortalParentWindowError::EmptyToken => {
+                f.write_str("the parent window is missing after its prefix")
+            }
+            ParsePortalParentWindowError::InvalidWindowId => {
+                f.write_str("the X11 window ID is not a nonzero hexadecimal number")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParsePortalParentWindowError {}
+
+impl RawWindowHandle {
+    /// The `parent_window` to pass to xdg-desktop-portal for dialogs belonging to this window.
+    ///
+    /// Returns `None` for handles without a window ID. Wayland surfaces have to be exported with
+    /// `zxdg_exporter_v2`, whose handle can then be passed as [`PortalParentWindow::Wayland`].
+    pub fn to_portal_parent_window(&self) -> Option<PortalParentWindow<'static>> {
+        match self {
+            RawWindowHandle::Xlib(handle) => handle.window.map(PortalParentWindow::X11),
+            RawWindowHandle::Xcb(handle) => handle
+                .window
+                .map(|window| PortalParentWindow::X11(window.into())),
+            _ => None,
+        }
+    }
+}
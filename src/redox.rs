@@ -1,25 +1,44 @@
-use core::ptr;
-use libc::c_void;
+use core::ffi::c_void;
+use core::ptr::NonNull;
 
-/// Raw window handle for Redox OS.
+/// Raw window handle for Orbital, the windowing system of Redox OS.
 ///
 /// ## Construction
 /// ```
-/// # use raw_window_handle::redox::RedoxHandle;
-/// let mut handle = RedoxHandle::empty()
-///  /* set fields */
+/// # use raw_window_handle::redox::OrbitalHandle;
+/// let mut handle = OrbitalHandle::empty();
+/// /* set fields */
 /// ```
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct RedoxHandle {
-    /// A pointer to an orbclient window.
-    pub window: *mut c_void,
+pub struct OrbitalHandle {
+    /// A pointer to an orbclient `Window`.
+    pub window: Option<NonNull<c_void>>,
 }
 
-impl RedoxHandle {
-    pub fn empty() -> RedoxHandle {
-        RedoxHandle {
-            window: ptr::null_mut(),
-        }
+impl OrbitalHandle {
+    pub fn empty() -> OrbitalHandle {
+        OrbitalHandle { window: None }
+    }
+}
+
+/// Raw display handle for Orbital.
+///
+/// This stands for the connection to the `orbital:` scheme. orbclient opens that scheme once per
+/// window, so there is no connection object independent of a window, and this handle has no
+/// fields yet.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::redox::OrbitalDisplayHandle;
+/// let handle = OrbitalDisplayHandle::empty();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrbitalDisplayHandle {}
+
+impl OrbitalDisplayHandle {
+    pub fn empty() -> OrbitalDisplayHandle {
+        OrbitalDisplayHandle {}
     }
 }
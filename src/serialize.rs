@@ -0,0 +1,402 @@
+use core::convert::TryFrom;
+use core::ffi::c_void;
+use core::fmt;
+use core::num::{NonZeroU32, NonZeroU64};
+use core::ptr::NonNull;
+
+use cty::{c_int, c_ulong};
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    android, ios, linux, macos, redox, unix, web, windows, RawWindowHandle, RawWindowHandleKind,
+    WindowRole,
+};
+
+/// The serialized form of a [`RawWindowHandle`], with only the fields that mean the same in every
+/// process.
+// The variants are named after those of `RawWindowHandle`.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "RawWindowHandle", deny_unknown_fields)]
+enum Portable {
+    IOS {},
+    MacOS {},
+    Orbital {},
+    Xlib {
+        window: Option<NonZeroU64>,
+        parent: Option<NonZeroU64>,
+        role: Option<WindowRole>,
+        visual_id: u64,
+        depth: Option<u8>,
+        colormap: Option<u64>,
+        screen: Option<c_int>,
+    },
+    Xcb {
+        window: Option<NonZeroU32>,
+        parent: Option<NonZeroU32>,
+        role: Option<WindowRole>,
+        visual_id: u32,
+        depth: Option<u8>,
+        colormap: Option<u32>,
+        screen: Option<c_int>,
+    },
+    Wayland {
+        role: Option<WindowRole>,
+    },
+    Windows {
+        hwnd: Option<NonZeroU64>,
+    },
+    WinRT {},
+    Web {
+        id: u32,
+    },
+    WebCanvas {},
+    WebOffscreenCanvas {},
+    Android {},
+    Drm {
+        plane: u32,
+        connector: u32,
+        crtc: u32,
+    },
+    Gbm {},
+}
+
+/// The error for handles that contain pointers or file descriptors.
+struct ProcessLocal(RawWindowHandleKind);
+
+impl fmt::Display for ProcessLocal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the {} handle contains pointers or file descriptors that are only valid in this \
+             process, clear them with `RawWindowHandle::without_process_local` to serialize it",
+            self.0
+        )
+    }
+}
+
+impl RawWindowHandle {
+    /// This handle with all fields cleared that are only meaningful in the current process, like
+    /// pointers and file descriptors.
+    ///
+    /// Serializing a handle fails if it contains such fields, calling this first opts in to leaving
+    /// them out. What remains are global identifiers like X11 window IDs, `HWND`s and
+    /// [`WebHandle::id`](web::WebHandle::id), and values like the window's role.
+    ///
+    /// ## Example
+    /// ```
+    /// # use core::num::{NonZeroU32, NonZeroU64};
+    /// # use raw_window_handle::{RawWindowHandle, WindowRole};
+    /// # use raw_window_handle::{android::*, ios::*, linux::*, macos::*, redox::*, unix::*};
+    /// # use raw_window_handle::{web::*, windows::*};
+    /// let pointer = core::ptr::NonNull::new(0x1000 as *mut _);
+    ///
+    /// let mut xlib = XlibHandle::new(0x3a00007, 0x1000 as *mut _).unwrap();
+    /// xlib.parent = NonZeroU64::new(0x3a00001);
+    /// xlib.role = Some(WindowRole::Popup);
+    /// xlib.visual_id = 0x21;
+    /// xlib.depth = Some(24);
+    /// xlib.colormap = Some(0);
+    /// xlib.screen = Some(1);
+    /// let mut xcb = XcbHandle::new(0x3a00007, 0x1000 as *mut _).unwrap();
+    /// xcb.parent = NonZeroU32::new(0x3a00001);
+    /// xcb.role = Some(WindowRole::Child);
+    /// xcb.visual_id = 0x21;
+    /// xcb.depth = Some(0);
+    /// xcb.screen = Some(0);
+    /// let mut wayland = WaylandHandle::new(0x1000 as *mut _, 0x2000 as *mut _).unwrap();
+    /// wayland.role = Some(WindowRole::Toplevel);
+    /// let mut windows = WindowsHandle::empty();
+    /// windows.hwnd = core::ptr::NonNull::new(0x1d0a2c as *mut _);
+    /// windows.hinstance = pointer;
+    /// let mut web = WebHandle::empty();
+    /// web.id = 7;
+    /// let mut drm = DrmHandle::empty();
+    /// drm.fd = 3;
+    /// drm.plane = 31;
+    /// drm.connector = 77;
+    /// drm.crtc = 42;
+    /// let mut ios = IOSHandle::empty();
+    /// ios.ui_view = pointer;
+    /// let mut macos = MacOSHandle::empty();
+    /// macos.ns_view = pointer;
+    /// let mut orbital = OrbitalHandle::empty();
+    /// orbital.window = pointer;
+    /// let mut winrt = WinRTHandle::empty();
+    /// winrt.core_window = pointer;
+    /// let mut canvas = WebCanvasHandle::empty();
+    /// canvas.obj = pointer;
+    /// let mut offscreen = WebOffscreenCanvasHandle::empty();
+    /// offscreen.obj = pointer;
+    /// let mut android = AndroidHandle::empty();
+    /// android.a_native_window = pointer;
+    /// let mut gbm = GbmHandle::empty();
+    /// gbm.gbm_surface = pointer;
+    ///
+    /// let handles = [
+    ///     RawWindowHandle::IOS(ios),
+    ///     RawWindowHandle::MacOS(macos),
+    ///     RawWindowHandle::Orbital(orbital),
+    ///     RawWindowHandle::Xlib(xlib),
+    ///     RawWindowHandle::Xcb(xcb),
+    ///     RawWindowHandle::Wayland(wayland),
+    ///     RawWindowHandle::Windows(windows),
+    ///     RawWindowHandle::WinRT(winrt),
+    ///     RawWindowHandle::Web(web),
+    ///     RawWindowHandle::WebCanvas(canvas),
+    ///     RawWindowHandle::WebOffscreenCanvas(offscreen),
+    ///     RawWindowHandle::Android(android),
+    ///     RawWindowHandle::Drm(drm),
+    ///     RawWindowHandle::Gbm(gbm),
+    /// ];
+    /// // Only `WebHandle` has nothing to leave out.
+    /// for handle in handles.iter() {
+    ///     match serde_json::to_string(handle) {
+    ///         Ok(_) => assert!(matches!(handle, RawWindowHandle::Web(_))),
+    ///         Err(error) => assert!(error.to_string().contains("without_process_local")),
+    ///     }
+    /// }
+    ///
+    /// let expected = [
+    ///     r#"{"IOS":{}}"#,
+    ///     r#"{"MacOS":{}}"#,
+    ///     r#"{"Orbital":{}}"#,
+    ///     r#"{"Xlib":{"window":60817415,"parent":60817409,"role":"Popup","visual_id":33,"depth":24,"colormap":0,"screen":1}}"#,
+    ///     r#"{"Xcb":{"window":60817415,"parent":60817409,"role":"Child","visual_id":33,"depth":0,"colormap":null,"screen":0}}"#,
+    ///     r#"{"Wayland":{"role":"Toplevel"}}"#,
+    ///     r#"{"Windows":{"hwnd":1903148}}"#,
+    ///     r#"{"WinRT":{}}"#,
+    ///     r#"{"Web":{"id":7}}"#,
+    ///     r#"{"WebCanvas":{}}"#,
+    ///     r#"{"WebOffscreenCanvas":{}}"#,
+    ///     r#"{"Android":{}}"#,
+    ///     r#"{"Drm":{"plane":31,"connector":77,"crtc":42}}"#,
+    ///     r#"{"Gbm":{}}"#,
+    /// ];
+    /// for (handle, expected) in handles.iter().zip(expected.iter()) {
+    ///     let portable = handle.without_process_local();
+    ///     assert_eq!(portable.kind(), handle.kind());
+    ///
+    ///     let json = serde_json::to_string(&portable).unwrap();
+    ///     assert_eq!(json, *expected);
+    ///     assert_eq!(serde_json::from_str::<RawWindowHandle>(&json).unwrap(), portable);
+    /// }
+    ///
+    /// // Pointers aren't accepted when deserializing either.
+    /// let json = r#"{"Wayland":{"surface":4096,"role":null}}"#;
+    /// assert!(serde_json::from_str::<RawWindowHandle>(json).is_err());
+    /// ```
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "serde")))]
+    pub fn without_process_local(&self) -> RawWindowHandle {
+        match *self {
+            RawWindowHandle::IOS(_) => RawWindowHandle::IOS(ios::IOSHandle::empty()),
+            RawWindowHandle::MacOS(_) => RawWindowHandle::MacOS(macos::MacOSHandle::empty()),
+            RawWindowHandle::Orbital(_) => RawWindowHandle::Orbital(redox::OrbitalHandle::empty()),
+            RawWindowHandle::Xlib(mut handle) => {
+                handle.display = None;
+                RawWindowHandle::Xlib(handle)
+            }
+            RawWindowHandle::Xcb(mut handle) => {
+                handle.connection = None;
+                RawWindowHandle::Xcb(handle)
+            }
+            RawWindowHandle::Wayland(handle) => {
+                let mut portable = unix::WaylandHandle::empty();
+                portable.role = handle.role;
+                RawWindowHandle::Wayland(portable)
+            }
+            RawWindowHandle::Windows(mut handle) => {
+                handle.hinstance = None;
+                RawWindowHandle::Windows(handle)
+            }
+            RawWindowHandle::WinRT(_) => RawWindowHandle::WinRT(windows::WinRTHandle::empty()),
+            RawWindowHandle::Web(handle) => RawWindowHandle::Web(handle),
+            RawWindowHandle::WebCanvas(_) => {
+                RawWindowHandle::WebCanvas(web::WebCanvasHandle::empty())
+            }
+            RawWindowHandle::WebOffscreenCanvas(_) => {
+                RawWindowHandle::WebOffscreenCanvas(web::WebOffscreenCanvasHandle::empty())
+            }
+            RawWindowHandle::Android(_) => {
+                RawWindowHandle::Android(android::AndroidHandle::empty())
+            }
+            RawWindowHandle::Drm(mut handle) => {
+                handle.fd = -1;
+                RawWindowHandle::Drm(handle)
+            }
+            RawWindowHandle::Gbm(_) => RawWindowHandle::Gbm(linux::GbmHandle::empty()),
+        }
+    }
+}
+
+impl TryFrom<RawWindowHandle> for Portable {
+    type Error = ProcessLocal;
+
+    fn try_from(raw: RawWindowHandle) -> Result<Self, Self::Error> {
+        if raw.without_process_local() != raw {
+            return Err(ProcessLocal(raw.kind()));
+        }
+
+        // `c_ulong` is only 32 bits wide on some targets.
+        #[allow(clippy::useless_conversion)]
+        let portable = match raw {
+            RawWindowHandle::IOS(_) => Portable::IOS {},
+            RawWindowHandle::MacOS(_) => Portable::MacOS {},
+            RawWindowHandle::Orbital(_) => Portable::Orbital {},
+            RawWindowHandle::Xlib(handle) => Portable::Xlib {
+                window: handle.window,
+                parent: handle.parent,
+                role: handle.role,
+                visual_id: u64::from(handle.visual_id),
+                depth: handle.depth,
+                colormap: handle.colormap.map(u64::from),
+                screen: handle.screen,
+            },
+            RawWindowHandle::Xcb(handle) => Portable::Xcb {
+                window: handle.window,
+                parent: handle.parent,
+                role: handle.role,
+                visual_id: handle.visual_id,
+                depth: handle.depth,
+                colormap: handle.colormap,
+                screen: handle.screen,
+            },
+            RawWindowHandle::Wayland(handle) => Portable::Wayland { role: handle.role },
+            RawWindowHandle::Windows(handle) => Portable::Windows {
+                hwnd: handle.hwnd.map(|hwnd| {
+                    NonZeroU64::new(hwnd.as_ptr() as usize as u64)
+                        .expect("non-null pointers are nonzero")
+                }),
+            },
+            RawWindowHandle::WinRT(_) => Portable::WinRT {},
+            RawWindowHandle::Web(handle) => Portable::Web { id: handle.id },
+            RawWindowHandle::WebCanvas(_) => Portable::WebCanvas {},
+            RawWindowHandle::WebOffscreenCanvas(_) => Portable::WebOffscreenCanvas {},
+            RawWindowHandle::Android(_) => Portable::Android {},
+            RawWindowHandle::Drm(handle) => Portable::Drm {
+                plane: handle.plane,
+                connector: handle.connector,
+                crtc: handle.crtc,
+            },
+            RawWindowHandle::Gbm(_) => Portable::Gbm {},
+        };
+        Ok(portable)
+    }
+}
+
+impl TryFrom<Portable> for RawWindowHandle {
+    type Error = &'static str;
+
+    fn try_from(portable: Portable) -> Result<Self, Self::Error> {
+        let raw = match portable {
+            Portable::IOS {} => RawWindowHandle::IOS(ios::IOSHandle::empty()),
+            Portable::MacOS {} => RawWindowHandle::MacOS(macos::MacOSHandle::empty()),
+            Portable::Orbital {} => RawWindowHandle::Orbital(redox::OrbitalHandle::empty()),
+            Portable::Xlib {
+                window,
+                parent,
+                role,
+                visual_id,
+                depth,
+                colormap,
+                screen,
+            } => {
+                let too_large = "the X11 ID doesn't fit in a `c_ulong`";
+                let mut handle = unix::XlibHandle::empty();
+                handle.window = window;
+                handle.parent = parent;
+                handle.role = role;
+                handle.visual_id = c_ulong::try_from(visual_id).map_err(|_| too_large)?;
+                handle.depth = depth;
+                handle.colormap = colormap
+                    .map(c_ulong::try_from)
+                    .transpose()
+                    .map_err(|_| too_large)?;
+                handle.screen = screen;
+                RawWindowHandle::Xlib(handle)
+            }
+            Portable::Xcb {
+                window,
+                parent,
+                role,
+                visual_id,
+                depth,
+                colormap,
+                screen,
+            } => {
+                let mut handle = unix::XcbHandle::empty();
+                handle.window = window;
+                handle.parent = parent;
+                handle.role = role;
+                handle.visual_id = visual_id;
+                handle.depth = depth;
+                handle.colormap = colormap;
+                handle.screen = screen;
+                RawWindowHandle::Xcb(handle)
+            }
+            Portable::Wayland { role } => {
+                let mut handle = unix::WaylandHandle::empty();
+                handle.role = role;
+                RawWindowHandle::Wayland(handle)
+            }
+            Portable::Windows { hwnd } => {
+                let mut handle = windows::WindowsHandle::empty();
+                if let Some(hwnd) = hwnd {
+                    let hwnd = usize::try_from(hwnd.get())
+                        .map_err(|_| "the `HWND` doesn't fit in a pointer")?;
+                    handle.hwnd = NonNull::new(hwnd as *mut c_void);
+                }
+                RawWindowHandle::Windows(handle)
+            }
+            Portable::WinRT {} => RawWindowHandle::WinRT(windows::WinRTHandle::empty()),
+            Portable::Web { id } => {
+                let mut handle = web::WebHandle::empty();
+                handle.id = id;
+                RawWindowHandle::Web(handle)
+            }
+            Portable::WebCanvas {} => RawWindowHandle::WebCanvas(web::WebCanvasHandle::empty()),
+            Portable::WebOffscreenCanvas {} => {
+                RawWindowHandle::WebOffscreenCanvas(web::WebOffscreenCanvasHandle::empty())
+            }
+            Portable::Android {} => RawWindowHandle::Android(android::AndroidHandle::empty()),
+            Portable::Drm {
+                plane,
+                connector,
+                crtc,
+            } => {
+                let mut handle = linux::DrmHandle::empty();
+                handle.plane = plane;
+                handle.connector = connector;
+                handle.crtc = crtc;
+                RawWindowHandle::Drm(handle)
+            }
+            Portable::Gbm {} => RawWindowHandle::Gbm(linux::GbmHandle::empty()),
+        };
+        Ok(raw)
+    }
+}
+
+/// Serializes the handle as an externally tagged enum, e.g. `{"Xlib": {"window": 60817415, ..}}`
+/// in JSON.
+///
+/// Fails if the handle contains pointers or file descriptors, which are only valid in the current
+/// process. [`RawWindowHandle::without_process_local`] clears them.
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "serde")))]
+impl Serialize for RawWindowHandle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Portable::try_from(*self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// Deserializes a handle serialized by the [`Serialize`] impl. Pointers and file descriptors are
+/// left empty.
+#[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for RawWindowHandle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawWindowHandle::try_from(Portable::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
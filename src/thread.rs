@@ -0,0 +1,207 @@
+use core::marker::PhantomData;
+
+use crate::{HandleError, RawWindowHandle};
+
+/// Proof that the current thread is the application's main thread.
+///
+/// AppKit and UIKit objects such as `NSView` and `UIView` may only be used on the main thread.
+/// This marker is neither `Send` nor `Sync`, so it can't leave the thread it was created on.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{HandleError, MainThreadMarker, RawWindowHandle};
+/// # use raw_window_handle::macos::MacOSHandle;
+/// const MAIN_THREAD_ID: u64 = 1;
+///
+/// let handle = RawWindowHandle::MacOS(MacOSHandle::empty());
+/// assert!(handle.is_main_thread_only());
+///
+/// // Simulate running on the main thread, and on another thread.
+/// let on_main = unsafe { MainThreadMarker::from_thread_ids(MAIN_THREAD_ID, MAIN_THREAD_ID) };
+/// let on_other = unsafe { MainThreadMarker::from_thread_ids(2, MAIN_THREAD_ID) };
+/// assert!(on_main.is_some());
+/// assert!(on_other.is_none());
+///
+/// assert_eq!(handle.check_thread(on_main), Ok(()));
+/// assert_eq!(handle.check_thread(on_other), Err(HandleError::WrongThread));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MainThreadMarker {
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl MainThreadMarker {
+    /// Create a marker if the current thread is the main thread.
+    ///
+    /// ## Example
+    /// ```
+    /// # use raw_window_handle::MainThreadMarker;
+    /// #[cfg(any(target_os = "macos", target_os = "ios"))]
+    /// {
+    ///     assert!(MainThreadMarker::new().is_some());
+    ///     std::thread::spawn(|| assert!(MainThreadMarker::new().is_none()))
+    ///         .join()
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    #[cfg_attr(
+        feature = "nightly-docs",
+        doc(cfg(any(target_os = "macos", target_os = "ios")))
+    )]
+    pub fn new() -> Option<Self> {
+        extern "C" {
+            fn pthread_main_np() -> cty::c_int;
+        }
+
+        if unsafe { pthread_main_np() } != 0 {
+            Some(unsafe { Self::new_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Create a marker without checking the current thread.
+    ///
+    /// ## Safety
+    /// The current thread must be the main thread.
+    pub const unsafe fn new_unchecked() -> Self {
+        Self {
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Create a marker if `current` identifies the main thread.
+    ///
+    /// The ids may come from any source that identifies threads uniquely, e.g. `pthread_self` or
+    /// the id that a windowing library recorded when it was initialized on the main thread. On
+    /// macOS and iOS, `MainThreadMarker::new` checks the current thread safely.
+    ///
+    /// ## Safety
+    /// `current` must identify the current thread, and `main` must identify the main thread.
+    pub unsafe fn from_thread_ids(current: u64, main: u64) -> Option<Self> {
+        if current == main {
+            Some(Self::new_unchecked())
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a handle may be used from a thread other than the one it was obtained on.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossThreadUse {
+    /// The handle may be used from any thread, e.g. Wayland and XCB objects, which are
+    /// thread-safe proxies.
+    Allowed,
+    /// The handle may be used from any thread only if `XInitThreads` was called before any other
+    /// Xlib call.
+    AfterXInitThreads,
+    /// The handle may only be used on the thread that owns it, e.g. the main thread for AppKit and
+    /// UIKit.
+    Forbidden,
+}
+
+/// A window handle that may be sent to and shared with other threads.
+///
+/// [`RawWindowHandle`] is neither `Send` nor `Sync`, as most variants contain raw pointers. This
+/// wrapper opts into both, after the creator asserts that the handle may be used across threads.
+///
+/// ## Example
+/// ```
+/// # use raw_window_handle::{CrossThreadUse, RawWindowHandle, SendableWindowHandle};
+/// # use raw_window_handle::macos::MacOSHandle;
+/// # use raw_window_handle::unix::{WaylandHandle, XlibHandle};
+/// let wayland = RawWindowHandle::Wayland(WaylandHandle::empty());
+/// assert_eq!(wayland.cross_thread_use(), CrossThreadUse::Allowed);
+/// let sendable = SendableWindowHandle::try_new(wayland).unwrap();
+/// let sent = std::thread::spawn(move || sendable).join().unwrap();
+/// assert_eq!(sent.raw(), wayland);
+///
+/// let xlib = RawWindowHandle::Xlib(XlibHandle::empty());
+/// assert_eq!(xlib.cross_thread_use(), CrossThreadUse::AfterXInitThreads);
+/// assert!(SendableWindowHandle::try_new(xlib).is_none());
+///
+/// let macos = RawWindowHandle::MacOS(MacOSHandle::empty());
+/// assert_eq!(macos.cross_thread_use(), CrossThreadUse::Forbidden);
+/// assert!(SendableWindowHandle::try_new(macos).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SendableWindowHandle {
+    raw: RawWindowHandle,
+}
+
+unsafe impl Send for SendableWindowHandle {}
+unsafe impl Sync for SendableWindowHandle {}
+
+impl SendableWindowHandle {
+    /// Assert that the handle may be used from any thread.
+    ///
+    /// ## Safety
+    /// Depending on [`RawWindowHandle::cross_thread_use`]:
+    ///
+    /// - [`CrossThreadUse::Allowed`]: always sound, see [`SendableWindowHandle::try_new`].
+    /// - [`CrossThreadUse::AfterXInitThreads`]: `XInitThreads` must have been called before any
+    ///   other Xlib call was made.
+    /// - [`CrossThreadUse::Forbidden`]: the handle must only be used on the thread that owns it,
+    ///   e.g. by dispatching to the main thread for AppKit and UIKit handles.
+    pub const unsafe fn new(raw: RawWindowHandle) -> Self {
+        Self { raw }
+    }
+
+    /// Wrap the handle if it may always be used from any thread.
+    pub fn try_new(raw: RawWindowHandle) -> Option<Self> {
+        match raw.cross_thread_use() {
+            CrossThreadUse::Allowed => Some(Self { raw }),
+            _ => None,
+        }
+    }
+
+    /// Get the underlying raw window handle.
+    pub const fn raw(&self) -> RawWindowHandle {
+        self.raw
+    }
+}
+
+impl RawWindowHandle {
+    /// Whether this handle may be used from a thread other than the one it was obtained on.
+    pub fn cross_thread_use(&self) -> CrossThreadUse {
+        match self {
+            RawWindowHandle::Xlib(_) => CrossThreadUse::AfterXInitThreads,
+            RawWindowHandle::Xcb(_)
+            | RawWindowHandle::Wayland(_)
+            | RawWindowHandle::Windows(_)
+            | RawWindowHandle::Android(_)
+            | RawWindowHandle::Drm(_) => CrossThreadUse::Allowed,
+            RawWindowHandle::IOS(_)
+            | RawWindowHandle::MacOS(_)
+            | RawWindowHandle::Orbital(_)
+            | RawWindowHandle::WinRT(_)
+            | RawWindowHandle::Web(_)
+            | RawWindowHandle::WebCanvas(_)
+            | RawWindowHandle::WebOffscreenCanvas(_)
+            | RawWindowHandle::Gbm(_) => CrossThreadUse::Forbidden,
+        }
+    }
+
+    /// Whether the objects referred to by this handle may only be used on the main thread.
+    ///
+    /// This is the case for AppKit and UIKit handles.
+    pub fn is_main_thread_only(&self) -> bool {
+        matches!(self, RawWindowHandle::MacOS(_) | RawWindowHandle::IOS(_))
+    }
+
+    /// Check whether this handle may be used on the current thread.
+    ///
+    /// `main_thread` should be a [`MainThreadMarker`] if the current thread is the main thread.
+    /// Fails with [`HandleError::WrongThread`] if the handle is main-thread-only and no marker
+    /// is given.
+    pub fn check_thread(&self, main_thread: Option<MainThreadMarker>) -> Result<(), HandleError> {
+        if self.is_main_thread_only() && main_thread.is_none() {
+            Err(HandleError::WrongThread)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -1,7 +1,59 @@
 use core::ffi::c_void;
-use core::ptr;
+use core::num::{NonZeroU32, NonZeroU64};
+use core::ptr::NonNull;
 
-use cty::c_ulong;
+use cty::{c_int, c_ulong};
+
+use crate::WindowRole;
+
+#[cfg(all(
+    feature = "x11-xcb",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris"
+    )
+))]
+mod convert;
+#[cfg(all(
+    any(feature = "x11-query", feature = "x11-reconnect", feature = "x11-xcb"),
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris"
+    )
+))]
+mod dl;
+#[cfg(all(
+    feature = "x11-query",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris"
+    )
+))]
+mod query;
+#[cfg(all(
+    feature = "x11-reconnect",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris"
+    )
+))]
+mod reconnect;
 
 /// Raw window handle for Xlib.
 ///
@@ -15,9 +67,21 @@ use cty::c_ulong;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct XlibHandle {
     /// An Xlib `Window`.
-    pub window: c_ulong,
+    pub window: Option<NonZeroU64>,
     /// A pointer to an Xlib `Display`.
-    pub display: *mut c_void,
+    pub display: Option<NonNull<c_void>>,
+    /// The Xlib `Window` this window is a child of, or transient for.
+    pub parent: Option<NonZeroU64>,
+    /// The role of this window relative to `parent`.
+    pub role: Option<WindowRole>,
+    /// The `VisualID` of the window's visual, or `0` if unknown.
+    pub visual_id: c_ulong,
+    /// The depth of the window in bits per pixel, which is `0` for `InputOnly` windows.
+    pub depth: Option<u8>,
+    /// The `Colormap` of the window, which is `0` if the window has none.
+    pub colormap: Option<c_ulong>,
+    /// The number of the screen the window was created on.
+    pub screen: Option<c_int>,
 }
 
 /// Raw window handle for Xcb.
@@ -32,9 +96,21 @@ pub struct XlibHandle {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct XcbHandle {
     /// An X11 `xcb_window_t`.
-    pub window: u32, // Based on xproto.h
+    pub window: Option<NonZeroU32>, // Based on xproto.h
     /// A pointer to an X server `xcb_connection_t`.
-    pub connection: *mut c_void,
+    pub connection: Option<NonNull<c_void>>,
+    /// The `xcb_window_t` this window is a child of, or transient for.
+    pub parent: Option<NonZeroU32>,
+    /// The role of this window relative to `parent`.
+    pub role: Option<WindowRole>,
+    /// The `xcb_visualid_t` of the window's visual, or `0` if unknown.
+    pub visual_id: u32,
+    /// The depth of the window in bits per pixel, which is `0` for `InputOnly` windows.
+    pub depth: Option<u8>,
+    /// The `xcb_colormap_t` of the window, which is `0` if the window has none.
+    pub colormap: Option<u32>,
+    /// The number of the screen the window was created on.
+    pub screen: Option<c_int>,
 }
 
 /// Raw window handle for Wayland.
@@ -49,34 +125,234 @@ pub struct XcbHandle {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct WaylandHandle {
     /// A pointer to a `wl_surface`.
-    pub surface: *mut c_void,
+    pub surface: Option<NonNull<c_void>>,
     /// A pointer to a `wl_display`.
-    pub display: *mut c_void,
+    pub display: Option<NonNull<c_void>>,
+    /// A pointer to the `wl_surface` of the parent of this surface's `xdg_popup` or
+    /// `xdg_toplevel`, or of this surface's `wl_subsurface`.
+    pub parent: Option<NonNull<c_void>>,
+    /// The role of this surface relative to `parent`.
+    pub role: Option<WindowRole>,
+}
+
+/// Raw display handle for Xlib.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::unix::XlibDisplayHandle;
+/// let handle = XlibDisplayHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XlibDisplayHandle {
+    /// A pointer to an Xlib `Display`.
+    pub display: Option<NonNull<c_void>>,
+    /// The default screen number of the display.
+    pub screen: c_int,
+}
+
+/// Raw display handle for Xcb.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::unix::XcbDisplayHandle;
+/// let handle = XcbDisplayHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XcbDisplayHandle {
+    /// A pointer to an X server `xcb_connection_t`.
+    pub connection: Option<NonNull<c_void>>,
+    /// The default screen number of the connection.
+    pub screen: c_int,
+}
+
+/// Raw display handle for Wayland.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::unix::WaylandDisplayHandle;
+/// let handle = WaylandDisplayHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WaylandDisplayHandle {
+    /// A pointer to a `wl_display`.
+    pub display: Option<NonNull<c_void>>,
+}
+
+/// Raw monitor handle for Xlib.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::unix::XlibMonitorHandle;
+/// let handle = XlibMonitorHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XlibMonitorHandle {
+    /// A RandR `RROutput`.
+    pub output: c_ulong,
+    /// The RandR `RRCrtc` driving `output`.
+    pub crtc: c_ulong,
+}
+
+/// Raw monitor handle for Xcb.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::unix::XcbMonitorHandle;
+/// let handle = XcbMonitorHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XcbMonitorHandle {
+    /// A RandR `xcb_randr_output_t`.
+    pub output: u32,
+    /// The RandR `xcb_randr_crtc_t` driving `output`.
+    pub crtc: u32,
+}
+
+/// Raw monitor handle for Wayland.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::unix::WaylandMonitorHandle;
+/// let handle = WaylandMonitorHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WaylandMonitorHandle {
+    /// A pointer to a `wl_output`.
+    pub output: Option<NonNull<c_void>>,
 }
 
 impl XlibHandle {
+    /// Create a handle for `window` on `display`.
+    ///
+    /// Returns `None` if `window` is `0` or `display` is null.
+    ///
+    /// ```
+    /// # use raw_window_handle::unix::XlibHandle;
+    /// let display = 0x1000 as *mut _;
+    /// assert!(XlibHandle::new(0x3a00007, display).is_some());
+    /// assert!(XlibHandle::new(0, display).is_none());
+    /// assert!(XlibHandle::new(0x3a00007, core::ptr::null_mut()).is_none());
+    /// ```
+    pub fn new(window: c_ulong, display: *mut c_void) -> Option<XlibHandle> {
+        let mut handle = XlibHandle::empty();
+        // `c_ulong` is only 32 bits wide on some targets.
+        #[allow(clippy::useless_conversion)]
+        let window = u64::from(window);
+        handle.window = Some(NonZeroU64::new(window)?);
+        handle.display = Some(NonNull::new(display)?);
+        Some(handle)
+    }
+
     pub fn empty() -> XlibHandle {
         XlibHandle {
-            window: 0,
-            display: ptr::null_mut(),
+            window: None,
+            display: None,
+            parent: None,
+            role: None,
+            visual_id: 0,
+            depth: None,
+            colormap: None,
+            screen: None,
         }
     }
 }
 
 impl XcbHandle {
+    /// Create a handle for `window` on `connection`.
+    ///
+    /// Returns `None` if `window` is `0` or `connection` is null.
+    pub fn new(window: u32, connection: *mut c_void) -> Option<XcbHandle> {
+        let mut handle = XcbHandle::empty();
+        handle.window = Some(NonZeroU32::new(window)?);
+        handle.connection = Some(NonNull::new(connection)?);
+        Some(handle)
+    }
+
     pub fn empty() -> XcbHandle {
         XcbHandle {
-            window: 0,
-            connection: ptr::null_mut(),
+            window: None,
+            connection: None,
+            parent: None,
+            role: None,
+            visual_id: 0,
+            depth: None,
+            colormap: None,
+            screen: None,
         }
     }
 }
 
 impl WaylandHandle {
+    /// Create a handle for `surface` on `display`.
+    ///
+    /// Returns `None` if either pointer is null.
+    pub fn new(surface: *mut c_void, display: *mut c_void) -> Option<WaylandHandle> {
+        let mut handle = WaylandHandle::empty();
+        handle.surface = Some(NonNull::new(surface)?);
+        handle.display = Some(NonNull::new(display)?);
+        Some(handle)
+    }
+
     pub fn empty() -> WaylandHandle {
         WaylandHandle {
-            surface: ptr::null_mut(),
-            display: ptr::null_mut(),
+            surface: None,
+            display: None,
+            parent: None,
+            role: None,
+        }
+    }
+}
+
+impl XlibDisplayHandle {
+    pub fn empty() -> XlibDisplayHandle {
+        XlibDisplayHandle {
+            display: None,
+            screen: 0,
         }
     }
 }
+
+impl XcbDisplayHandle {
+    pub fn empty() -> XcbDisplayHandle {
+        XcbDisplayHandle {
+            connection: None,
+            screen: 0,
+        }
+    }
+}
+
+impl WaylandDisplayHandle {
+    pub fn empty() -> WaylandDisplayHandle {
+        WaylandDisplayHandle { display: None }
+    }
+}
+
+impl XlibMonitorHandle {
+    pub fn empty() -> XlibMonitorHandle {
+        XlibMonitorHandle { output: 0, crtc: 0 }
+    }
+}
+
+impl XcbMonitorHandle {
+    pub fn empty() -> XcbMonitorHandle {
+        XcbMonitorHandle { output: 0, crtc: 0 }
+    }
+}
+
+impl WaylandMonitorHandle {
+    pub fn empty() -> WaylandMonitorHandle {
+        WaylandMonitorHandle { output: None }
+    }
+}
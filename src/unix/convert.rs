@@ -0,0 +1,145 @@
+//! Converting between Xlib and XCB handles with `libX11-xcb`.
+
+use core::convert::TryFrom;
+use core::ffi::c_void;
+use core::num::{NonZeroU32, NonZeroU64};
+use core::ptr::NonNull;
+
+use cty::c_ulong;
+
+use super::dl::xcb_connection;
+use super::{XcbDisplayHandle, XcbHandle, XlibDisplayHandle, XlibHandle};
+use crate::HandleError;
+
+/// Narrow an Xlib resource ID to the 32 bits used by the X protocol.
+fn xid(id: NonZeroU64) -> Result<NonZeroU32, HandleError> {
+    u32::try_from(id.get())
+        .ok()
+        .and_then(NonZeroU32::new)
+        .ok_or(HandleError::NotSupported)
+}
+
+impl XlibHandle {
+    /// The same window, used through the XCB connection underlying its `Display`.
+    ///
+    /// `libX11-xcb` is loaded at runtime. Fails with [`HandleError::Unavailable`] if `window` or
+    /// `display` is missing, and with [`HandleError::NotSupported`] if the library can't be
+    /// loaded.
+    ///
+    /// ## Example
+    /// This connects to the X server named by `$DISPLAY`, e.g. one started by `xvfb-run`,
+    /// and is skipped if `$DISPLAY` is unset.
+    /// ```
+    /// # use core::ffi::c_void;
+    /// # use raw_window_handle::HandleError;
+    /// # use raw_window_handle::unix::XlibHandle;
+    /// #[link(name = "X11")]
+    /// extern "C" {
+    ///     fn XOpenDisplay(name: *const u8) -> *mut c_void;
+    ///     fn XDefaultRootWindow(display: *mut c_void) -> u64;
+    ///     fn XCreateSimpleWindow(
+    ///         display: *mut c_void, parent: u64, x: i32, y: i32, width: u32, height: u32,
+    ///         border_width: u32, border: u64, background: u64,
+    ///     ) -> u64;
+    ///     fn XCloseDisplay(display: *mut c_void) -> i32;
+    /// }
+    ///
+    /// assert_eq!(unsafe { XlibHandle::empty().to_xcb() }, Err(HandleError::Unavailable));
+    ///
+    /// if std::env::var_os("DISPLAY").is_none() {
+    ///     return; // No X server available.
+    /// }
+    /// let display = unsafe { XOpenDisplay(core::ptr::null()) };
+    /// assert!(!display.is_null(), "can't connect to $DISPLAY");
+    ///
+    /// let window =
+    ///     unsafe { XCreateSimpleWindow(display, XDefaultRootWindow(display), 0, 0, 1, 1, 0, 0, 0) };
+    /// let xlib = XlibHandle::new(window, display).unwrap();
+    /// let xcb = unsafe { xlib.to_xcb() }.unwrap();
+    /// assert_eq!(xcb.window.map(|window| u64::from(window.get())), Some(window));
+    /// assert_eq!(unsafe { xcb.to_xlib(display) }, Ok(xlib));
+    ///
+    /// // A different `Display` doesn't wrap the window's connection.
+    /// let other = unsafe { XOpenDisplay(core::ptr::null()) };
+    /// assert_eq!(unsafe { xcb.to_xlib(other) }, Err(HandleError::NotSupported));
+    /// unsafe { XCloseDisplay(other) };
+    /// unsafe { XCloseDisplay(display) };
+    /// ```
+    ///
+    /// ## Safety
+    /// `display` must be a valid Xlib `Display`.
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "x11-xcb")))]
+    pub unsafe fn to_xcb(&self) -> Result<XcbHandle, HandleError> {
+        let (window, display) = match (self.window, self.display) {
+            (Some(window), Some(display)) => (window, display),
+            _ => return Err(HandleError::Unavailable),
+        };
+
+        let mut handle = XcbHandle::empty();
+        handle.window = Some(xid(window)?);
+        handle.connection = Some(xcb_connection(display)?);
+        handle.parent = self.parent.map(xid).transpose()?;
+        handle.role = self.role;
+        handle.visual_id = u32::try_from(self.visual_id).map_err(|_| HandleError::NotSupported)?;
+        handle.depth = self.depth;
+        handle.colormap = self
+            .colormap
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|_| HandleError::NotSupported)?;
+        handle.screen = self.screen;
+        Ok(handle)
+    }
+}
+
+impl XcbHandle {
+    /// The same window, used through an Xlib `Display` that wraps its XCB connection.
+    ///
+    /// `libX11-xcb` is loaded at runtime. Fails with [`HandleError::Unavailable`] if `window`,
+    /// `connection` or `display` is missing, and with [`HandleError::NotSupported`] if the library
+    /// can't be loaded or `display` doesn't wrap `connection`.
+    ///
+    /// ## Safety
+    /// `display` must be a valid Xlib `Display` or null.
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "x11-xcb")))]
+    pub unsafe fn to_xlib(&self, display: *mut c_void) -> Result<XlibHandle, HandleError> {
+        let (window, connection, display) =
+            match (self.window, self.connection, NonNull::new(display)) {
+                (Some(window), Some(connection), Some(display)) => (window, connection, display),
+                _ => return Err(HandleError::Unavailable),
+            };
+        if xcb_connection(display)? != connection {
+            return Err(HandleError::NotSupported);
+        }
+
+        let mut handle = XlibHandle::empty();
+        handle.window = Some(window.into());
+        handle.display = Some(display);
+        handle.parent = self.parent.map(NonZeroU64::from);
+        handle.role = self.role;
+        handle.visual_id = self.visual_id.into();
+        handle.depth = self.depth;
+        handle.colormap = self.colormap.map(c_ulong::from);
+        handle.screen = self.screen;
+        Ok(handle)
+    }
+}
+
+impl XlibDisplayHandle {
+    /// The XCB connection underlying this `Display`.
+    ///
+    /// `libX11-xcb` is loaded at runtime. Fails with [`HandleError::Unavailable`] if `display` is
+    /// missing, and with [`HandleError::NotSupported`] if the library can't be loaded.
+    ///
+    /// ## Safety
+    /// `display` must be a valid Xlib `Display`.
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "x11-xcb")))]
+    pub unsafe fn to_xcb(&self) -> Result<XcbDisplayHandle, HandleError> {
+        let display = self.display.ok_or(HandleError::Unavailable)?;
+
+        let mut handle = XcbDisplayHandle::empty();
+        handle.connection = Some(xcb_connection(display)?);
+        handle.screen = self.screen;
+        Ok(handle)
+    }
+}
@@ -0,0 +1,72 @@
+//! Minimal runtime loading of shared libraries, so that no X11 library is needed at link time.
+
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use cty::{c_char, c_int};
+
+#[cfg(any(feature = "x11-query", feature = "x11-xcb"))]
+use crate::HandleError;
+
+const RTLD_NOW: c_int = 2;
+
+extern "C" {
+    fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlclose(handle: *mut c_void) -> c_int;
+    #[cfg(feature = "x11-query")]
+    pub fn free(ptr: *mut c_void);
+}
+
+/// A shared library that stays loaded until this value is dropped.
+pub struct Library(NonNull<c_void>);
+
+impl Library {
+    /// Load the first of `names` that can be found. The names must be nul-terminated.
+    pub fn open(names: &[&[u8]]) -> Option<Library> {
+        names
+            .iter()
+            .find_map(|name| {
+                debug_assert_eq!(name.last(), Some(&0));
+                NonNull::new(unsafe { dlopen(name.as_ptr().cast(), RTLD_NOW) })
+            })
+            .map(Library)
+    }
+
+    /// Look up a symbol in the library. The name must be nul-terminated.
+    ///
+    /// ## Safety
+    /// `T` must be a function pointer type matching the signature of the symbol.
+    pub unsafe fn sym<T: Copy>(&self, name: &[u8]) -> Option<T> {
+        debug_assert_eq!(name.last(), Some(&0));
+        let sym = dlsym(self.0.as_ptr(), name.as_ptr().cast());
+        if sym.is_null() {
+            None
+        } else {
+            Some(core::mem::transmute_copy(&sym))
+        }
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        unsafe { dlclose(self.0.as_ptr()) };
+    }
+}
+
+#[cfg(any(feature = "x11-query", feature = "x11-xcb"))]
+type XGetXCBConnection = unsafe extern "C" fn(*mut c_void) -> *mut c_void;
+
+/// The XCB connection underlying an Xlib `Display`, from `libX11-xcb`.
+///
+/// ## Safety
+/// `display` must be a valid Xlib `Display`.
+#[cfg(any(feature = "x11-query", feature = "x11-xcb"))]
+pub unsafe fn xcb_connection(display: NonNull<c_void>) -> Result<NonNull<c_void>, HandleError> {
+    let xlib_xcb = Library::open(&[b"libX11-xcb.so.1\0", b"libX11-xcb.so\0"])
+        .ok_or(HandleError::NotSupported)?;
+    let get_xcb_connection: XGetXCBConnection = xlib_xcb
+        .sym(b"XGetXCBConnection\0")
+        .ok_or(HandleError::NotSupported)?;
+    NonNull::new(get_xcb_connection(display.as_ptr())).ok_or(HandleError::Unavailable)
+}
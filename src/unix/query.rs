@@ -0,0 +1,301 @@
+//! Querying missing window attributes from the X server.
+
+use core::convert::TryFrom;
+use core::ffi::c_void;
+use core::num::NonZeroU32;
+use core::ptr;
+
+use cty::{c_int, c_uint, c_ulong};
+
+use super::dl::{free, xcb_connection, Library};
+use super::{XcbHandle, XlibHandle};
+use crate::HandleError;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct XcbCookie {
+    sequence: c_uint,
+}
+
+#[repr(C)]
+struct XcbGetWindowAttributesReply {
+    response_type: u8,
+    backing_store: u8,
+    sequence: u16,
+    length: u32,
+    visual: u32,
+    class: u16,
+    bit_gravity: u8,
+    win_gravity: u8,
+    backing_planes: u32,
+    backing_pixel: u32,
+    save_under: u8,
+    map_is_installed: u8,
+    map_state: u8,
+    override_redirect: u8,
+    colormap: u32,
+    all_event_masks: u32,
+    your_event_mask: u32,
+    do_not_propagate_mask: u16,
+    pad0: [u8; 2],
+}
+
+#[repr(C)]
+struct XcbGetGeometryReply {
+    response_type: u8,
+    depth: u8,
+    sequence: u16,
+    length: u32,
+    root: u32,
+    x: i16,
+    y: i16,
+    width: u16,
+    height: u16,
+    border_width: u16,
+    pad0: [u8; 2],
+}
+
+#[repr(C)]
+struct XcbScreenIterator {
+    data: *const u32,
+    rem: c_int,
+    index: c_int,
+}
+
+type XcbRequest = unsafe extern "C" fn(*mut c_void, u32) -> XcbCookie;
+type XcbReply<T> = unsafe extern "C" fn(*mut c_void, XcbCookie, *mut *mut c_void) -> *mut T;
+type XcbGetSetup = unsafe extern "C" fn(*mut c_void) -> *const c_void;
+type XcbSetupRootsIterator = unsafe extern "C" fn(*const c_void) -> XcbScreenIterator;
+type XcbScreenNext = unsafe extern "C" fn(*mut XcbScreenIterator);
+
+impl XlibHandle {
+    /// Fill in whichever of `visual_id`, `depth`, `colormap` and `screen` are missing by querying
+    /// the X server.
+    ///
+    /// The query goes through the XCB connection underlying `display`, so that an unknown window
+    /// is reported as an error value instead of going to the Xlib error handler, which exits the
+    /// process by default. `libX11-xcb` and `libxcb` are loaded at runtime. Fails with
+    /// [`HandleError::NotSupported`] if they can't be loaded, and with
+    /// [`HandleError::Destroyed`] if the server doesn't know the window.
+    ///
+    /// ## Example
+    /// This connects to the X server named by `$DISPLAY`, e.g. one started by `xvfb-run`,
+    /// and is skipped if `$DISPLAY` is unset.
+    /// ```
+    /// # use core::ffi::c_void;
+    /// # use raw_window_handle::HandleError;
+    /// # use raw_window_handle::unix::XlibHandle;
+    /// #[link(name = "X11")]
+    /// extern "C" {
+    ///     fn XOpenDisplay(name: *const u8) -> *mut c_void;
+    ///     fn XDefaultRootWindow(display: *mut c_void) -> u64;
+    ///     fn XDefaultScreen(display: *mut c_void) -> i32;
+    ///     fn XCreateSimpleWindow(
+    ///         display: *mut c_void, parent: u64, x: i32, y: i32, width: u32, height: u32,
+    ///         border_width: u32, border: u64, background: u64,
+    ///     ) -> u64;
+    ///     fn XDestroyWindow(display: *mut c_void, window: u64) -> i32;
+    ///     fn XCloseDisplay(display: *mut c_void) -> i32;
+    /// }
+    ///
+    /// if std::env::var_os("DISPLAY").is_none() {
+    ///     return; // No X server available.
+    /// }
+    /// let display = unsafe { XOpenDisplay(core::ptr::null()) };
+    /// assert!(!display.is_null(), "can't connect to $DISPLAY");
+    ///
+    /// let window =
+    ///     unsafe { XCreateSimpleWindow(display, XDefaultRootWindow(display), 0, 0, 1, 1, 0, 0, 0) };
+    /// let mut handle = XlibHandle::new(window, display).unwrap();
+    /// unsafe { handle.query_missing_attributes() }.unwrap();
+    ///
+    /// assert_ne!(handle.visual_id, 0);
+    /// assert_ne!(handle.depth, Some(0));
+    /// assert!(handle.colormap.is_some());
+    /// assert_eq!(handle.screen, Some(unsafe { XDefaultScreen(display) }));
+    ///
+    /// // An unknown window is reported as an error instead of exiting the process.
+    /// unsafe { XDestroyWindow(display, window) };
+    /// let mut handle = XlibHandle::new(window, display).unwrap();
+    /// assert_eq!(unsafe { handle.query_missing_attributes() }, Err(HandleError::Destroyed));
+    /// unsafe { XCloseDisplay(display) };
+    /// ```
+    ///
+    /// ## Safety
+    /// `display` must be a valid Xlib `Display` and `window` must be a window on it.
+    pub unsafe fn query_missing_attributes(&mut self) -> Result<(), HandleError> {
+        if self.visual_id != 0
+            && self.depth.is_some()
+            && self.colormap.is_some()
+            && self.screen.is_some()
+        {
+            return Ok(());
+        }
+        let (window, display) = match (self.window, self.display) {
+            (Some(window), Some(display)) => (window, display),
+            _ => return Err(HandleError::Unavailable),
+        };
+
+        // Window IDs are 32 bits on the wire, so the server can't know larger ones.
+        let mut xcb = XcbHandle::empty();
+        xcb.window = Some(
+            u32::try_from(window.get())
+                .ok()
+                .and_then(NonZeroU32::new)
+                .ok_or(HandleError::Destroyed)?,
+        );
+        xcb.connection = Some(xcb_connection(display)?);
+        xcb.query_missing_attributes()?;
+
+        if self.visual_id == 0 {
+            self.visual_id = c_ulong::from(xcb.visual_id);
+        }
+        self.depth = self.depth.or(xcb.depth);
+        self.colormap = self.colormap.or(xcb.colormap.map(c_ulong::from));
+        self.screen = self.screen.or(xcb.screen);
+        Ok(())
+    }
+}
+
+impl XcbHandle {
+    /// Fill in whichever of `visual_id`, `depth`, `colormap` and `screen` are missing by querying
+    /// the X server.
+    ///
+    /// `libxcb` is loaded at runtime. Fails with [`HandleError::NotSupported`] if it can't be
+    /// loaded, and with [`HandleError::Destroyed`] if the server doesn't know the window.
+    ///
+    /// ## Example
+    /// This connects to the X server named by `$DISPLAY`, e.g. one started by `xvfb-run`,
+    /// and is skipped if `$DISPLAY` is unset.
+    /// ```
+    /// # use core::ffi::c_void;
+    /// # use raw_window_handle::unix::XcbHandle;
+    /// #[repr(C)]
+    /// struct ScreenIterator {
+    ///     data: *const u32,
+    ///     rem: i32,
+    ///     index: i32,
+    /// }
+    ///
+    /// #[link(name = "xcb")]
+    /// extern "C" {
+    ///     fn xcb_connect(name: *const u8, screen: *mut i32) -> *mut c_void;
+    ///     fn xcb_connection_has_error(connection: *mut c_void) -> i32;
+    ///     fn xcb_get_setup(connection: *mut c_void) -> *const c_void;
+    ///     fn xcb_setup_roots_iterator(setup: *const c_void) -> ScreenIterator;
+    ///     fn xcb_screen_next(iterator: *mut ScreenIterator);
+    ///     fn xcb_generate_id(connection: *mut c_void) -> u32;
+    ///     fn xcb_create_window(
+    ///         connection: *mut c_void, depth: u8, window: u32, parent: u32, x: i16, y: i16,
+    ///         width: u16, height: u16, border_width: u16, class: u16, visual: u32,
+    ///         value_mask: u32, values: *const c_void,
+    ///     ) -> u32;
+    ///     fn xcb_disconnect(connection: *mut c_void);
+    /// }
+    ///
+    /// if std::env::var_os("DISPLAY").is_none() {
+    ///     return; // No X server available.
+    /// }
+    /// let mut screen = 0;
+    /// let connection = unsafe { xcb_connect(core::ptr::null(), &mut screen) };
+    /// assert_eq!(unsafe { xcb_connection_has_error(connection) }, 0, "can't connect to $DISPLAY");
+    ///
+    /// let mut roots = unsafe { xcb_setup_roots_iterator(xcb_get_setup(connection)) };
+    /// for _ in 0..screen {
+    ///     unsafe { xcb_screen_next(&mut roots) };
+    /// }
+    /// let root = unsafe { *roots.data };
+    ///
+    /// let window = unsafe { xcb_generate_id(connection) };
+    /// unsafe {
+    ///     xcb_create_window(
+    ///         connection, 0, window, root, 0, 0, 1, 1, 0, 0, 0, 0, core::ptr::null(),
+    ///     )
+    /// };
+    /// let mut handle = XcbHandle::new(window, connection).unwrap();
+    /// unsafe { handle.query_missing_attributes() }.unwrap();
+    ///
+    /// assert_ne!(handle.visual_id, 0);
+    /// assert_ne!(handle.depth, Some(0));
+    /// assert!(handle.colormap.is_some());
+    /// assert_eq!(handle.screen, Some(screen));
+    /// unsafe { xcb_disconnect(connection) };
+    /// ```
+    ///
+    /// ## Safety
+    /// `connection` must be a valid `xcb_connection_t`.
+    pub unsafe fn query_missing_attributes(&mut self) -> Result<(), HandleError> {
+        if self.visual_id != 0
+            && self.depth.is_some()
+            && self.colormap.is_some()
+            && self.screen.is_some()
+        {
+            return Ok(());
+        }
+        let (window, connection) = match (self.window, self.connection) {
+            (Some(window), Some(connection)) => (window.get(), connection.as_ptr()),
+            _ => return Err(HandleError::Unavailable),
+        };
+
+        let xcb =
+            Library::open(&[b"libxcb.so.1\0", b"libxcb.so\0"]).ok_or(HandleError::NotSupported)?;
+        let get_window_attributes: XcbRequest = xcb
+            .sym(b"xcb_get_window_attributes\0")
+            .ok_or(HandleError::NotSupported)?;
+        let get_window_attributes_reply: XcbReply<XcbGetWindowAttributesReply> = xcb
+            .sym(b"xcb_get_window_attributes_reply\0")
+            .ok_or(HandleError::NotSupported)?;
+        let get_geometry: XcbRequest = xcb
+            .sym(b"xcb_get_geometry\0")
+            .ok_or(HandleError::NotSupported)?;
+        let get_geometry_reply: XcbReply<XcbGetGeometryReply> = xcb
+            .sym(b"xcb_get_geometry_reply\0")
+            .ok_or(HandleError::NotSupported)?;
+        let get_setup: XcbGetSetup = xcb
+            .sym(b"xcb_get_setup\0")
+            .ok_or(HandleError::NotSupported)?;
+        let setup_roots_iterator: XcbSetupRootsIterator = xcb
+            .sym(b"xcb_setup_roots_iterator\0")
+            .ok_or(HandleError::NotSupported)?;
+        let screen_next: XcbScreenNext = xcb
+            .sym(b"xcb_screen_next\0")
+            .ok_or(HandleError::NotSupported)?;
+
+        let attributes_cookie = get_window_attributes(connection, window);
+        let geometry_cookie = get_geometry(connection, window);
+
+        let mut error = ptr::null_mut();
+        let attributes = get_window_attributes_reply(connection, attributes_cookie, &mut error);
+        free(error);
+        let mut error = ptr::null_mut();
+        let geometry = get_geometry_reply(connection, geometry_cookie, &mut error);
+        free(error);
+        if attributes.is_null() || geometry.is_null() {
+            free(attributes.cast());
+            free(geometry.cast());
+            return Err(HandleError::Destroyed);
+        }
+
+        if self.visual_id == 0 {
+            self.visual_id = (*attributes).visual;
+        }
+        self.colormap = self.colormap.or(Some((*attributes).colormap));
+        self.depth = self.depth.or(Some((*geometry).depth));
+        let root = (*geometry).root;
+        free(attributes.cast());
+        free(geometry.cast());
+
+        // The first field of an `xcb_screen_t` is its root window. The iterator's `index` is a
+        // byte offset, not the screen number, so count the screens here.
+        let mut roots = setup_roots_iterator(get_setup(connection));
+        let mut number = 0;
+        while self.screen.is_none() && roots.rem > 0 {
+            if *roots.data == root {
+                self.screen = Some(number);
+            }
+            screen_next(&mut roots);
+            number += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+//! Reconnecting to the X server of an exported window with `libX11`.
+
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use cty::{c_char, c_int};
+
+use super::dl::Library;
+use super::XlibHandle;
+use crate::{ExportedWindowHandle, HandleError, OwnedWindowHandle, RawWindowHandle};
+
+type XOpenDisplay = unsafe extern "C" fn(*const c_char) -> *mut c_void;
+type XDefaultScreen = unsafe extern "C" fn(*mut c_void) -> c_int;
+type XCloseDisplay = unsafe extern "C" fn(*mut c_void) -> c_int;
+
+fn open_xlib() -> Result<Library, HandleError> {
+    Library::open(&[b"libX11.so.6\0", b"libX11.so\0"]).ok_or(HandleError::NotSupported)
+}
+
+impl ExportedWindowHandle<'_> {
+    /// Import the handle into this process over a new connection to the X server it was exported
+    /// from.
+    ///
+    /// `libX11` is loaded at runtime and stays loaded. The `Display` is opened with the handle's
+    /// `display_name`, and closed when the returned handle is dropped. The `screen` of the
+    /// returned [`XlibHandle`] is the default screen of that `Display`. Fails with
+    /// [`HandleError::NotSupported`] for non-X11 handles and if `libX11` can't be loaded, and with
+    /// [`HandleError::Unavailable`] if the X server can't be reached.
+    ///
+    /// The window itself isn't checked, so it may have been destroyed since it was exported.
+    ///
+    /// ## Example
+    /// This exports a window to a child process, which connects to the X server named by
+    /// `$DISPLAY`, e.g. one started by `xvfb-run`. It is skipped if `$DISPLAY` is unset.
+    /// ```
+    /// # #[cfg(all(feature = "x11-query", target_os = "linux"))]
+    /// # fn main() {
+    /// # use core::convert::TryFrom;
+    /// # use core::ffi::c_void;
+    /// # use std::process::Command;
+    /// # use raw_window_handle::{ExportedWindowHandle, HasRawWindowHandle, RawWindowHandle};
+    /// # use raw_window_handle::unix::XlibHandle;
+    /// #[link(name = "X11")]
+    /// extern "C" {
+    ///     fn XOpenDisplay(name: *const u8) -> *mut c_void;
+    ///     fn XDefaultRootWindow(display: *mut c_void) -> u64;
+    ///     fn XDefaultScreen(display: *mut c_void) -> i32;
+    ///     fn XDisplayString(display: *mut c_void) -> *const u8;
+    ///     fn XCreateSimpleWindow(
+    ///         display: *mut c_void, parent: u64, x: i32, y: i32, width: u32, height: u32,
+    ///         border_width: u32, border: u64, background: u64,
+    ///     ) -> u64;
+    ///     fn XSync(display: *mut c_void, discard: i32) -> i32;
+    ///     fn XCloseDisplay(display: *mut c_void) -> i32;
+    /// }
+    ///
+    /// if let Ok(token) = std::env::var("RAW_WINDOW_HANDLE_EXPORTED") {
+    ///     // The child process.
+    ///     let exported = ExportedWindowHandle::parse(&token).unwrap();
+    ///     let owned = unsafe { exported.reconnect() }.unwrap();
+    ///     let mut handle = XlibHandle::try_from(owned.raw_window_handle()).unwrap();
+    ///     assert_eq!(
+    ///         handle.screen,
+    ///         Some(unsafe { XDefaultScreen(handle.display.unwrap().as_ptr()) }),
+    ///     );
+    ///
+    ///     handle.screen = None;
+    ///     unsafe { handle.query_missing_attributes() }.unwrap();
+    ///     assert_ne!(handle.visual_id, 0);
+    ///     drop(owned);
+    ///     return;
+    /// }
+    ///
+    /// if std::env::var_os("DISPLAY").is_none() {
+    ///     return; // No X server available.
+    /// }
+    /// let display = unsafe { XOpenDisplay(core::ptr::null()) };
+    /// assert!(!display.is_null(), "can't connect to $DISPLAY");
+    ///
+    /// let window =
+    ///     unsafe { XCreateSimpleWindow(display, XDefaultRootWindow(display), 0, 0, 1, 1, 0, 0, 0) };
+    /// unsafe { XSync(display, 0) };
+    /// let display_name = unsafe { std::ffi::CStr::from_ptr(XDisplayString(display).cast()) };
+    /// let raw = RawWindowHandle::Xlib(XlibHandle::new(window, display).unwrap());
+    /// let exported = ExportedWindowHandle::export(raw, display_name.to_str().unwrap()).unwrap();
+    ///
+    /// let status = Command::new(std::env::current_exe().unwrap())
+    ///     .env("RAW_WINDOW_HANDLE_EXPORTED", exported.to_string())
+    ///     .status()
+    ///     .unwrap();
+    /// assert!(status.success());
+    /// unsafe { XCloseDisplay(display) };
+    /// # }
+    /// # #[cfg(not(all(feature = "x11-query", target_os = "linux")))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// ## Safety
+    /// The window must not be destroyed before the returned handle is dropped.
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "x11-reconnect")))]
+    pub unsafe fn reconnect(&self) -> Result<OwnedWindowHandle, HandleError> {
+        let (window, display_name) = match *self {
+            ExportedWindowHandle::X11 {
+                window,
+                display_name,
+            } => (window, display_name),
+            _ => return Err(HandleError::NotSupported),
+        };
+
+        // `XOpenDisplay` takes a nul-terminated string, and display names are short.
+        let mut name = [0u8; 256];
+        if display_name.len() >= name.len() || display_name.contains('\0') {
+            return Err(HandleError::NotSupported);
+        }
+        name[..display_name.len()].copy_from_slice(display_name.as_bytes());
+
+        let xlib = open_xlib()?;
+        let open_display: XOpenDisplay = xlib
+            .sym(b"XOpenDisplay\0")
+            .ok_or(HandleError::NotSupported)?;
+        let default_screen: XDefaultScreen = xlib
+            .sym(b"XDefaultScreen\0")
+            .ok_or(HandleError::NotSupported)?;
+        let display =
+            NonNull::new(open_display(name.as_ptr().cast())).ok_or(HandleError::Unavailable)?;
+        // Unloading `libX11` while the `Display` is open would leave it dangling.
+        core::mem::forget(xlib);
+
+        let mut handle = XlibHandle::empty();
+        handle.window = Some(window.into());
+        handle.display = Some(display);
+        handle.screen = Some(default_screen(display.as_ptr()));
+        Ok(OwnedWindowHandle::new(
+            RawWindowHandle::Xlib(handle),
+            close_display,
+        ))
+    }
+}
+
+/// Release callback for the handles returned by [`ExportedWindowHandle::reconnect`].
+unsafe fn close_display(raw: RawWindowHandle) {
+    let display = match raw {
+        RawWindowHandle::Xlib(XlibHandle {
+            display: Some(display),
+            ..
+        }) => display,
+        _ => return,
+    };
+    // `libX11` is still loaded, so this finds the library that opened the `Display`.
+    if let Ok(xlib) = open_xlib() {
+        if let Some(close_display) = xlib.sym::<XCloseDisplay>(b"XCloseDisplay\0") {
+            close_display(display.as_ptr());
+        }
+    }
+}
@@ -1,3 +1,18 @@
+use core::ffi::c_void;
+use core::fmt;
+use core::num::NonZeroU32;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+#[cfg(feature = "web-sys")]
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use std::{sync::Mutex, vec::Vec};
+#[cfg(feature = "web-sys")]
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+#[cfg(feature = "web-sys")]
+use web_sys::{HtmlCanvasElement, OffscreenCanvas};
+
 /// Raw window handle for the web
 ///
 /// ## Construction
@@ -14,12 +29,259 @@ pub struct WebHandle {
     /// When accessing from JS, the attribute will automatically be called rawHandle
     ///
     /// Each canvas created by the windowing system should be assigned their own unique ID.
-    /// 0 should be reserved for invalid / null IDs.
+    /// 0 should be reserved for invalid / null IDs. [`allocate_id`] hands out IDs that don't
+    /// collide with those of other libraries using it, see its documentation for the limits.
     pub id: u32,
 }
 
+/// Raw window handle for an `HtmlCanvasElement` on the web.
+///
+/// Unlike [`WebHandle`], this refers to the canvas directly, so consumers don't need to look it up
+/// in the DOM. With the `web-sys` feature, [`from_canvas`](Self::from_canvas) and
+/// [`canvas`](Self::canvas) convert from and to a `web_sys::HtmlCanvasElement`.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::web::WebCanvasHandle;
+/// let mut handle = WebCanvasHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WebCanvasHandle {
+    /// A pointer to a `JsValue` of wasm-bindgen 0.2 referring to an `HtmlCanvasElement`.
+    ///
+    /// A `JsValue` is an index into wasm-bindgen's table of JS objects, whose layout is only
+    /// guaranteed within wasm-bindgen 0.2, so providers and consumers have to use that version.
+    /// The `JsValue` is owned by the provider, consumers may only borrow it.
+    pub obj: Option<NonNull<c_void>>,
+}
+
+/// Raw window handle for an `OffscreenCanvas` on the web.
+///
+/// An `OffscreenCanvas` may also be used inside a web worker, where there is no DOM. With the
+/// `web-sys` feature, [`from_canvas`](Self::from_canvas) and [`canvas`](Self::canvas) convert
+/// from and to a `web_sys::OffscreenCanvas`.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::web::WebOffscreenCanvasHandle;
+/// let mut handle = WebOffscreenCanvasHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WebOffscreenCanvasHandle {
+    /// A pointer to a `JsValue` of wasm-bindgen 0.2 referring to an `OffscreenCanvas`.
+    ///
+    /// A `JsValue` is an index into wasm-bindgen's table of JS objects, whose layout is only
+    /// guaranteed within wasm-bindgen 0.2, so providers and consumers have to use that version.
+    /// The `JsValue` is owned by the provider, consumers may only borrow it.
+    pub obj: Option<NonNull<c_void>>,
+}
+
 impl WebHandle {
     pub fn empty() -> WebHandle {
         WebHandle { id: 0 }
     }
+
+    /// Get the CSS selector matching the canvas of this handle.
+    ///
+    /// With the `web-sys` feature, [`canvas`](Self::canvas) looks up the canvas with it.
+    ///
+    /// ```
+    /// # use raw_window_handle::web::WebHandle;
+    /// let mut handle = WebHandle::empty();
+    /// handle.id = 7;
+    /// assert_eq!(handle.selector().to_string(), r#"canvas[data-raw-handle="7"]"#);
+    /// ```
+    pub fn selector(&self) -> CanvasSelector {
+        CanvasSelector { id: self.id }
+    }
+
+    /// Find the canvas of this handle in the document, using [`selector`](Self::selector).
+    ///
+    /// Returns `None` if the ID is 0, if there is no document, e.g. in a web worker, and if no
+    /// canvas has a matching `data-raw-handle` attribute.
+    #[cfg(feature = "web-sys")]
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "web-sys")))]
+    pub fn canvas(&self) -> Option<HtmlCanvasElement> {
+        if self.id == 0 {
+            return None;
+        }
+        let document = web_sys::window()?.document()?;
+        let element = document
+            .query_selector(&self.selector().to_string())
+            .ok()??;
+        element.dyn_into().ok()
+    }
+}
+
+/// The CSS selector matching the canvas of a [`WebHandle`], as returned by
+/// [`WebHandle::selector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CanvasSelector {
+    id: u32,
+}
+
+impl fmt::Display for CanvasSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "canvas[data-raw-handle=\"{}\"]", self.id)
+    }
+}
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+#[cfg(feature = "std")]
+static FREE_IDS: Mutex<Vec<NonZeroU32>> = Mutex::new(Vec::new());
+
+/// Allocate a unique ID for [`WebHandle::id`].
+///
+/// The IDs are unique among the libraries that allocate them here, as long as they link the same
+/// semver-compatible version of this crate into the same wasm module. Libraries that pick IDs on
+/// their own, use another major version of this crate, or live in another wasm module on the page
+/// may still hand out colliding IDs.
+///
+/// With the `std` feature, IDs returned to [`release_id`] are reused. Without it released IDs are
+/// never handed out again, so a page that keeps creating canvases eventually runs out of IDs.
+///
+/// ```
+/// # use raw_window_handle::web;
+/// let first = web::allocate_id();
+/// let second = web::allocate_id();
+/// assert_ne!(first, second);
+/// ```
+///
+/// # Panics
+///
+/// Panics if all IDs are in use.
+pub fn allocate_id() -> NonZeroU32 {
+    #[cfg(feature = "std")]
+    {
+        if let Some(id) = FREE_IDS.lock().unwrap_or_else(|e| e.into_inner()).pop() {
+            return id;
+        }
+    }
+
+    NEXT_ID
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| id.checked_add(1))
+        .ok()
+        .and_then(NonZeroU32::new)
+        .expect("all web handle IDs are in use")
+}
+
+/// Return an ID allocated by [`allocate_id`] once its canvas has been destroyed.
+///
+/// With the `std` feature the ID may be handed out again by [`allocate_id`], otherwise this does
+/// nothing. Releasing an ID that is already released, or that was never allocated, is ignored, so
+/// that it can't be handed out twice.
+///
+/// ```
+/// # use raw_window_handle::web;
+/// let id = web::allocate_id();
+/// web::release_id(id);
+/// # #[cfg(feature = "std")]
+/// assert_eq!(web::allocate_id(), id);
+///
+/// // Releasing an ID twice only makes it available once.
+/// web::release_id(id);
+/// web::release_id(id);
+/// assert_ne!(web::allocate_id(), web::allocate_id());
+///
+/// // IDs that were never allocated aren't handed out.
+/// let unallocated = core::num::NonZeroU32::new(u32::MAX).unwrap();
+/// web::release_id(unallocated);
+/// assert_ne!(web::allocate_id(), unallocated);
+/// ```
+pub fn release_id(id: NonZeroU32) {
+    #[cfg(feature = "std")]
+    {
+        let mut free = FREE_IDS.lock().unwrap_or_else(|e| e.into_inner());
+        if id.get() < NEXT_ID.load(Ordering::Relaxed) && !free.contains(&id) {
+            free.push(id);
+        }
+    }
+    #[cfg(not(feature = "std"))]
+    let _ = id;
+}
+
+impl WebCanvasHandle {
+    pub fn empty() -> WebCanvasHandle {
+        WebCanvasHandle { obj: None }
+    }
+
+    /// Create a handle referring to `canvas`.
+    ///
+    /// The handle borrows `canvas`, which has to outlive every use of the handle.
+    #[cfg(feature = "web-sys")]
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "web-sys")))]
+    pub fn from_canvas(canvas: &HtmlCanvasElement) -> WebCanvasHandle {
+        let value: &JsValue = canvas.as_ref();
+        WebCanvasHandle {
+            obj: Some(NonNull::from(value).cast()),
+        }
+    }
+
+    /// Get the canvas this handle refers to.
+    ///
+    /// Returns `None` if `obj` is null, and if it refers to something else than an
+    /// `HtmlCanvasElement`.
+    ///
+    /// ## Safety
+    /// `obj` must be null or point to a `JsValue` of wasm-bindgen 0.2, which must outlive the
+    /// returned reference.
+    #[cfg(feature = "web-sys")]
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "web-sys")))]
+    pub unsafe fn canvas(&self) -> Option<&HtmlCanvasElement> {
+        self.obj?.cast::<JsValue>().as_ref().dyn_ref()
+    }
+}
+
+impl WebOffscreenCanvasHandle {
+    pub fn empty() -> WebOffscreenCanvasHandle {
+        WebOffscreenCanvasHandle { obj: None }
+    }
+
+    /// Create a handle referring to `canvas`.
+    ///
+    /// The handle borrows `canvas`, which has to outlive every use of the handle.
+    #[cfg(feature = "web-sys")]
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "web-sys")))]
+    pub fn from_canvas(canvas: &OffscreenCanvas) -> WebOffscreenCanvasHandle {
+        let value: &JsValue = canvas.as_ref();
+        WebOffscreenCanvasHandle {
+            obj: Some(NonNull::from(value).cast()),
+        }
+    }
+
+    /// Get the canvas this handle refers to.
+    ///
+    /// Returns `None` if `obj` is null, and if it refers to something else than an
+    /// `OffscreenCanvas`.
+    ///
+    /// ## Safety
+    /// `obj` must be null or point to a `JsValue` of wasm-bindgen 0.2, which must outlive the
+    /// returned reference.
+    #[cfg(feature = "web-sys")]
+    #[cfg_attr(feature = "nightly-docs", doc(cfg(feature = "web-sys")))]
+    pub unsafe fn canvas(&self) -> Option<&OffscreenCanvas> {
+        self.obj?.cast::<JsValue>().as_ref().dyn_ref()
+    }
+}
+
+/// Raw display handle for the web.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::web::WebDisplayHandle;
+/// let handle = WebDisplayHandle::empty();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WebDisplayHandle {}
+
+impl WebDisplayHandle {
+    pub fn empty() -> WebDisplayHandle {
+        WebDisplayHandle {}
+    }
 }
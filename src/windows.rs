@@ -47,3 +47,41 @@ impl WinRTHandle {
         WinRTHandle { core_window: None }
     }
 }
+
+/// Raw display handle for Windows.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::windows::WindowsDisplayHandle;
+/// let handle = WindowsDisplayHandle::empty();
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowsDisplayHandle {}
+
+impl WindowsDisplayHandle {
+    pub fn empty() -> WindowsDisplayHandle {
+        WindowsDisplayHandle {}
+    }
+}
+
+/// Raw monitor handle for Windows.
+///
+/// ## Construction
+/// ```
+/// # use raw_window_handle::windows::WindowsMonitorHandle;
+/// let mut handle = WindowsMonitorHandle::empty();
+/// /* set fields */
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowsMonitorHandle {
+    /// A Win32 HMONITOR handle.
+    pub hmonitor: Option<NonNull<c_void>>,
+}
+
+impl WindowsMonitorHandle {
+    pub fn empty() -> WindowsMonitorHandle {
+        WindowsMonitorHandle { hmonitor: None }
+    }
+}
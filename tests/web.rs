@@ -0,0 +1,134 @@
+//! Tests for resolving web handles to their canvases.
+//!
+//! These run under Node with `wasm-bindgen-test`, which has no DOM, so a minimal one is installed
+//! by `install_dom`:
+//!
+//! ```sh
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test --target wasm32-unknown-unknown --features web-sys --test web
+//! ```
+#![cfg(all(target_arch = "wasm32", feature = "web-sys"))]
+
+use core::ptr::NonNull;
+
+use raw_window_handle::web::{WebCanvasHandle, WebHandle, WebOffscreenCanvasHandle};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::{HtmlCanvasElement, OffscreenCanvas};
+
+#[wasm_bindgen(inline_js = r#"
+class Element {
+    constructor(attributes) {
+        this.attributes = attributes;
+    }
+}
+class HTMLCanvasElement extends Element {}
+class OffscreenCanvas {}
+class Document {
+    constructor() {
+        this.elements = [];
+    }
+    querySelector(selector) {
+        const id = /^canvas\[data-raw-handle="(\d+)"\]$/.exec(selector);
+        if (id === null) {
+            throw new SyntaxError(`unsupported selector: ${selector}`);
+        }
+        const canvas = this.elements.find(
+            (element) =>
+                element instanceof HTMLCanvasElement &&
+                element.attributes["data-raw-handle"] === id[1],
+        );
+        return canvas === undefined ? null : canvas;
+    }
+}
+class Window {
+    static [Symbol.hasInstance](value) {
+        return value === globalThis;
+    }
+}
+
+export function install_dom() {
+    Object.assign(globalThis, { Element, HTMLCanvasElement, OffscreenCanvas, Window });
+    globalThis.document = new Document();
+}
+
+export function remove_dom() {
+    for (const name of ["Element", "HTMLCanvasElement", "OffscreenCanvas", "Window", "document"]) {
+        delete globalThis[name];
+    }
+}
+
+export function add_element(tag, id) {
+    const attributes = { "data-raw-handle": String(id) };
+    const element = tag === "canvas" ? new HTMLCanvasElement(attributes) : new Element(attributes);
+    globalThis.document.elements.push(element);
+    return element;
+}
+
+export function new_offscreen_canvas() {
+    return new OffscreenCanvas();
+}
+"#)]
+extern "C" {
+    fn install_dom();
+    fn remove_dom();
+    fn add_element(tag: &str, id: u32) -> JsValue;
+    fn new_offscreen_canvas() -> OffscreenCanvas;
+}
+
+#[wasm_bindgen_test]
+fn web_handle_resolves_its_canvas() {
+    install_dom();
+    add_element("div", 3);
+    let canvas: HtmlCanvasElement = add_element("canvas", 7).unchecked_into();
+
+    let mut handle = WebHandle::empty();
+    handle.id = 7;
+    assert_eq!(handle.canvas(), Some(canvas));
+
+    // Only canvases match.
+    handle.id = 3;
+    assert_eq!(handle.canvas(), None);
+    handle.id = 8;
+    assert_eq!(handle.canvas(), None);
+    handle.id = 0;
+    assert_eq!(handle.canvas(), None);
+}
+
+#[wasm_bindgen_test]
+fn web_handle_without_document() {
+    remove_dom();
+    let mut handle = WebHandle::empty();
+    handle.id = 7;
+    assert_eq!(handle.canvas(), None);
+}
+
+#[wasm_bindgen_test]
+fn canvas_handle_round_trip() {
+    install_dom();
+    let canvas: HtmlCanvasElement = add_element("canvas", 1).unchecked_into();
+
+    let handle = WebCanvasHandle::from_canvas(&canvas);
+    assert_eq!(unsafe { handle.canvas() }, Some(&canvas));
+    assert_eq!(unsafe { WebCanvasHandle::empty().canvas() }, None);
+
+    let offscreen = new_offscreen_canvas();
+    let mut handle = WebCanvasHandle::empty();
+    handle.obj = Some(NonNull::from(offscreen.as_ref() as &JsValue).cast());
+    assert_eq!(unsafe { handle.canvas() }, None);
+}
+
+#[wasm_bindgen_test]
+fn offscreen_canvas_handle_round_trip() {
+    install_dom();
+    let canvas = new_offscreen_canvas();
+
+    let handle = WebOffscreenCanvasHandle::from_canvas(&canvas);
+    assert_eq!(unsafe { handle.canvas() }, Some(&canvas));
+    assert_eq!(unsafe { WebOffscreenCanvasHandle::empty().canvas() }, None);
+
+    let element = add_element("canvas", 2);
+    let mut handle = WebOffscreenCanvasHandle::empty();
+    handle.obj = Some(NonNull::from(&element).cast());
+    assert_eq!(unsafe { handle.canvas() }, None);
+}